@@ -0,0 +1,106 @@
+use criterion::{
+    BenchmarkId, Criterion, black_box, criterion_group,
+    criterion_main,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+    widgets::{Widget, WidgetRef},
+};
+use tui_rule::{Bg, Rule, presets};
+fn gradient_rule() -> Rule {
+    Rule::from_set(presets::borders::double::TOP)
+        .with_gradient(colorgrad::preset::sinebow())
+        .area_margin(Margin::new(0, 0))
+}
+/// the rules of every group, the same ones so they can be compared
+fn rules() -> [(&'static str, Rule); 4] {
+    [
+        ("gradient", gradient_rule()),
+        (
+            "bg gradient",
+            gradient_rule().bg(Bg::GradientCustom(
+                Box::new(colorgrad::preset::viridis()),
+            )),
+        ),
+        ("label", gradient_rule().label("Logs")),
+        ("thick", gradient_rule().thickness(3)),
+    ]
+}
+/// a dashboard redrawing the same rules every frame
+fn reused(c: &mut Criterion) {
+    let mut group = c.benchmark_group("reused rule");
+    for width in [40, 200] {
+        let area = Rect::new(0, 0, width, 3);
+        let mut buf = Buffer::empty(area);
+        for (name, rule) in &rules() {
+            group.bench_with_input(
+                BenchmarkId::new(*name, width),
+                &area,
+                |b, area| {
+                    b.iter(|| {
+                        rule.render_ref(
+                            black_box(*area),
+                            &mut buf,
+                        )
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+/// the same rules with the gradient swapped before every render,
+/// so the colors are sampled every time like before the cache
+fn uncached(c: &mut Criterion) {
+    let mut group = c.benchmark_group("uncached rule");
+    for width in [40, 200] {
+        let area = Rect::new(0, 0, width, 3);
+        let mut buf = Buffer::empty(area);
+        for (name, rule) in rules() {
+            let mut rule = Some(rule);
+            group.bench_with_input(
+                BenchmarkId::new(name, width),
+                &area,
+                |b, area| {
+                    b.iter(|| {
+                        let swapped = rule
+                            .take()
+                            .unwrap()
+                            .with_gradient(
+                                colorgrad::preset::sinebow(
+                                ),
+                            );
+                        swapped.render_ref(
+                            black_box(*area),
+                            &mut buf,
+                        );
+                        rule = Some(swapped);
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+/// a rule built from scratch every frame, nothing carries over
+fn fresh(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fresh rule");
+    for width in [40, 200] {
+        let area = Rect::new(0, 0, width, 3);
+        let mut buf = Buffer::empty(area);
+        group.bench_with_input(
+            BenchmarkId::new("gradient", width),
+            &area,
+            |b, area| {
+                b.iter(|| {
+                    gradient_rule()
+                        .render(black_box(*area), &mut buf)
+                })
+            },
+        );
+    }
+    group.finish();
+}
+criterion_group!(benches, reused, uncached, fresh);
+criterion_main!(benches);
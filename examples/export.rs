@@ -0,0 +1,152 @@
+//! writes the images in `assets/` that the README and PRESET_EXAMPLES.md show.
+//! `cargo run --example export` after changing how rules are drawn
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Margin, Rect},
+    style::Style,
+    widgets::{Block, Widget, WidgetRef},
+};
+use std::{fs, io, path::Path};
+use tui_rule::{
+    Orientation, Rule, export, presets, registry,
+};
+fn main() -> io::Result<()> {
+    let assets = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets");
+    fs::create_dir_all(&assets)?;
+    let scenes = [
+        ("hor_gradient_padding", hor_gradient_padding()),
+        ("vert_gradient_padding", vert_gradient_padding()),
+        ("no_gradient", no_gradient()),
+        ("presets_horizontal", presets_horizontal()),
+        ("presets_vertical", presets_vertical()),
+        ("test_sets", test_sets()),
+    ];
+    for (name, buf) in scenes {
+        let path = assets.join(format!("{name}.svg"));
+        fs::write(&path, export::to_svg(&buf, buf.area))?;
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+/// a bordered block with `rules` rendered inside of it
+fn bordered(
+    width: u16,
+    height: u16,
+    rules: &[Rule],
+) -> Buffer {
+    let mut buf =
+        Buffer::empty(Rect::new(0, 0, width, height));
+    for rule in rules {
+        rule.render_ref(buf.area, &mut buf);
+    }
+    Block::bordered().render(buf.area, &mut buf);
+    buf
+}
+fn hor_gradient_padding() -> Buffer {
+    bordered(
+        60,
+        9,
+        &[Rule::from_set(presets::test_sets::HORIZONTAL)
+            .with_gradient(colorgrad::preset::warm())
+            .horizontal_padding(4)],
+    )
+}
+fn vert_gradient_padding() -> Buffer {
+    bordered(
+        60,
+        15,
+        &[Rule::from_set(presets::test_sets::VERTICAL)
+            .with_gradient(colorgrad::preset::warm())
+            .vertical()
+            .vertical_padding(1)],
+    )
+}
+fn no_gradient() -> Buffer {
+    bordered(
+        60,
+        15,
+        &[
+            Rule::from_set(presets::test_sets::HORIZONTAL)
+                .horizontal_padding(6),
+            Rule::from_set(presets::test_sets::VERTICAL)
+                .horizontal_alignment(Alignment::Left)
+                .vertical()
+                .horizontal_padding(4)
+                .vertical_padding(1),
+        ],
+    )
+}
+/// every horizontal preset, one per row next to its name
+fn presets_horizontal() -> Buffer {
+    let infos: Vec<_> = registry::all()
+        .into_iter()
+        .filter(|info| {
+            info.orientation == Orientation::Horizontal
+        })
+        .collect();
+    let name_width = infos
+        .iter()
+        .map(|i| i.name.len())
+        .max()
+        .unwrap_or(0) as u16
+        + 2;
+    let mut buf = Buffer::empty(Rect::new(
+        0,
+        0,
+        name_width + 40,
+        infos.len() as u16,
+    ));
+    for (y, info) in (0..).zip(&infos) {
+        buf.set_string(0, y, &info.name, Style::new());
+        Rule::from_set(info.set.clone())
+            .with_gradient(colorgrad::preset::sinebow())
+            .area_margin(Margin::new(0, 0))
+            .render_ref(
+                Rect::new(name_width, y, 40, 1),
+                &mut buf,
+            );
+    }
+    buf
+}
+/// every vertical preset, one per column in the order of the registry
+fn presets_vertical() -> Buffer {
+    let infos: Vec<_> = registry::all()
+        .into_iter()
+        .filter(|info| {
+            info.orientation == Orientation::Vertical
+        })
+        .collect();
+    let mut buf = Buffer::empty(Rect::new(
+        0,
+        0,
+        infos.len() as u16 * 3,
+        12,
+    ));
+    for (x, info) in (0..).step_by(3).zip(&infos) {
+        Rule::from_set(info.set.clone())
+            .with_gradient(colorgrad::preset::sinebow())
+            .area_margin(Margin::new(0, 0))
+            .vertical()
+            .render_ref(
+                Rect::new(x + 1, 0, 1, 12),
+                &mut buf,
+            );
+    }
+    buf
+}
+fn test_sets() -> Buffer {
+    bordered(
+        60,
+        15,
+        &[
+            Rule::from_set(presets::test_sets::HORIZONTAL)
+                .with_gradient(colorgrad::preset::warm())
+                .horizontal_padding(6),
+            Rule::from_set(presets::test_sets::VERTICAL)
+                .with_gradient(colorgrad::preset::cool())
+                .vertical()
+                .vertical_padding(1),
+        ],
+    )
+}
@@ -15,14 +15,11 @@ tui_rule::gen_example_code!(
             })?;
             let event = event::read()?;
 
-            if let Event::Key(key_event) = event {
-                if key_event.kind == KeyEventKind::Press {
-                    if let KeyCode::Char('q') =
-                        key_event.code
-                    {
-                        break Ok(());
-                    }
-                }
+            if let Event::Key(key_event) = event
+                && key_event.kind == KeyEventKind::Press
+                && let KeyCode::Char('q') = key_event.code
+            {
+                break Ok(());
             }
         }
     }
@@ -22,15 +22,11 @@ tui_rule::gen_example_code!(
                 f.render_widget(block, f.area());
             })?;
             let event = event::read()?;
-            if let Event::Key(key_event) = event {
-                if key_event.kind == KeyEventKind::Press {
-                    match key_event.code {
-                        KeyCode::Char('q') => {
-                            break Ok(());
-                        }
-                        _ => {}
-                    }
-                }
+            if let Event::Key(key_event) = event
+                && key_event.kind == KeyEventKind::Press
+                && let KeyCode::Char('q') = key_event.code
+            {
+                break Ok(());
             }
         }
     }
@@ -0,0 +1,38 @@
+tui_rule::gen_example_code!(
+    fn run(
+        terminal: &mut DefaultTerminal,
+    ) -> io::Result<()> {
+        loop {
+            terminal.draw(|f| {
+                let block =
+                    RuleBlock::from_preset("borders::rounded")
+                        .unwrap()
+                        .with_gradient(
+                            colorgrad::preset::sinebow(),
+                        )
+                        .title_top(
+                            Line::from("RuleBlock").centered(),
+                        )
+                        .title_bottom(
+                            Line::from("q to quit")
+                                .right_aligned(),
+                        )
+                        .title_left("tui-rule");
+                let rule = Rule::default()
+                    .with_gradient(colorgrad::preset::warm())
+                    .area_margin(ratatui::layout::Margin::new(
+                        0, 0,
+                    ));
+                f.render_widget(rule, block.inner(f.area()));
+                f.render_widget(block, f.area());
+            })?;
+            let event = event::read()?;
+            if let Event::Key(key_event) = event
+                && key_event.kind == KeyEventKind::Press
+                && let KeyCode::Char('q') = key_event.code
+            {
+                break Ok(());
+            }
+        }
+    }
+);
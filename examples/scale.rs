@@ -0,0 +1,43 @@
+tui_rule::gen_example_code!(
+    fn run(
+        terminal: &mut DefaultTerminal,
+    ) -> io::Result<()> {
+        loop {
+            terminal.draw(|f| {
+                let area = f.area();
+                let timeline = Scale::new(0.0, 60.0, 5.0)
+                    .format(|v| format!("{v}s"))
+                    .with_gradient(colorgrad::preset::warm());
+                let axis = Scale::new(1.0, 0.0, -0.1)
+                    .vertical()
+                    .labels(Side::Left)
+                    .with_gradient(colorgrad::preset::cool());
+                f.render_widget(
+                    &timeline,
+                    Rect::new(
+                        area.x + 6,
+                        area.bottom().saturating_sub(2),
+                        area.width.saturating_sub(6),
+                        2,
+                    ),
+                );
+                f.render_widget(
+                    &axis,
+                    Rect::new(
+                        area.x,
+                        area.y,
+                        6,
+                        area.height.saturating_sub(2),
+                    ),
+                );
+            })?;
+            let event = event::read()?;
+            if let Event::Key(key_event) = event
+                && key_event.kind == KeyEventKind::Press
+                && let KeyCode::Char('q') = key_event.code
+            {
+                break Ok(());
+            }
+        }
+    }
+);
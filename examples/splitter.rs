@@ -0,0 +1,58 @@
+tui_rule::gen_example_code!(
+    fn run(
+        terminal: &mut DefaultTerminal,
+    ) -> io::Result<()> {
+        use crossterm::event::{
+            DisableMouseCapture, EnableMouseCapture,
+        };
+        use ratatui::{layout::Layout, widgets::Paragraph};
+        crossterm::execute!(io::stdout(), EnableMouseCapture)?;
+        let mut state = SplitterState::new(30).min(10);
+        let result = loop {
+            terminal.draw(|f| {
+                let [left, _, right] =
+                    Layout::horizontal(state.constraints())
+                        .areas(f.area());
+                f.render_widget(
+                    Paragraph::new(
+                        "drag the splitter or click it and use the arrow keys",
+                    )
+                    .wrap(ratatui::widgets::Wrap { trim: true }),
+                    left,
+                );
+                f.render_widget(
+                    Paragraph::new("q to quit"),
+                    right,
+                );
+                f.render_stateful_widget(
+                    Splitter::new(
+                        Rule::new_vertical()
+                            .with_set(presets::vertical::ASCII)
+                            .with_gradient(
+                                colorgrad::preset::sinebow(),
+                            ),
+                    ),
+                    f.area(),
+                    &mut state,
+                );
+            })?;
+            match event::read()? {
+                Event::Mouse(mouse) => {
+                    state.handle_mouse(mouse);
+                }
+                Event::Key(key)
+                    if key.kind == KeyEventKind::Press
+                        && key.code == KeyCode::Char('q') =>
+                {
+                    break Ok(());
+                }
+                Event::Key(key) => {
+                    state.handle_key(key);
+                }
+                _ => {}
+            }
+        };
+        crossterm::execute!(io::stdout(), DisableMouseCapture)?;
+        result
+    }
+);
@@ -0,0 +1,238 @@
+//! ### Animated gradients
+//! rules driven by an explicit phase, e.g. the seconds since the app started.
+//! the same phase always renders the same frame
+//! ```rust
+//!     let start = Instant::now();
+//!     loop {
+//!         let rule = Rule::default()
+//!             .with_gradient(colorgrad::preset::sinebow())
+//!             .animation(Animation::scroll().speed(0.5))
+//!             .phase(start.elapsed().as_secs_f32());
+//!         terminal.draw(|f| f.render_widget(rule, f.area()))?;
+//!     }
+//! ```
+use colorgrad::{Color, Gradient};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+/// what the animation does to the gradient
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimationEffect {
+    /// the gradient flows along the rule.
+    /// `reflect` mirrors it at the ends instead of repeating it,
+    /// so gradients whose ends don't match have no seam
+    Scroll { reflect: bool },
+    /// the whole rule gets darker and brighter again.
+    /// `dim` is how much darker it gets at the lowest point (0 to 1)
+    Pulse { dim: f32 },
+    /// a highlight band moves along the rule.
+    /// `width` is a fraction of the rule, `strength` how close to white the band gets (0 to 1)
+    Shimmer { width: f32, strength: f32 },
+}
+/// which way the animation runs
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Hash)]
+pub enum AnimationDirection {
+    /// from the start of the rule to the end (top to bottom for vertical rules)
+    #[default]
+    Forward,
+    Backward,
+    /// forward, then backward
+    Alternate,
+}
+/// how the progress of each cycle is eased
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Hash)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+/// an animation layer for the rule's gradient
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default)
+)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Animation {
+    pub effect: AnimationEffect,
+    /// cycles per unit of phase. with the phase in seconds, cycles per second
+    pub speed: f32,
+    pub direction: AnimationDirection,
+    pub easing: Easing,
+}
+impl Default for Animation {
+    fn default() -> Self {
+        Self::scroll()
+    }
+}
+impl Easing {
+    /// eases `t` from 0 to 1
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+impl Animation {
+    fn new(effect: AnimationEffect) -> Self {
+        Self {
+            effect,
+            speed: 1.0,
+            direction: AnimationDirection::Forward,
+            easing: Easing::Linear,
+        }
+    }
+    /// a gradient flowing along the rule
+    pub fn scroll() -> Self {
+        Self::new(AnimationEffect::Scroll {
+            reflect: false,
+        })
+    }
+    /// a breathing brightness pulse
+    pub fn pulse() -> Self {
+        Self::new(AnimationEffect::Pulse { dim: 0.6 })
+    }
+    /// a highlight band moving along the rule
+    pub fn shimmer() -> Self {
+        Self::new(AnimationEffect::Shimmer {
+            width: 0.2,
+            strength: 0.7,
+        })
+    }
+    pub fn effect(
+        mut self,
+        effect: AnimationEffect,
+    ) -> Self {
+        self.effect = effect;
+        self
+    }
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+    pub fn direction(
+        mut self,
+        direction: AnimationDirection,
+    ) -> Self {
+        self.direction = direction;
+        self
+    }
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+    /// how far into the current cycle the animation is at `phase`, from 0 to 1
+    pub fn progress(&self, phase: f32) -> f32 {
+        let t = phase * self.speed;
+        match self.direction {
+            AnimationDirection::Forward => {
+                t.rem_euclid(1.0)
+            }
+            AnimationDirection::Backward => {
+                1.0 - t.rem_euclid(1.0)
+            }
+            AnimationDirection::Alternate => {
+                let t = t.rem_euclid(2.0);
+                if t < 1.0 { t } else { 2.0 - t }
+            }
+        }
+    }
+    /// the gradient as it looks at `phase`
+    pub fn apply<'a>(
+        &self,
+        gradient: &'a dyn Gradient,
+        phase: f32,
+    ) -> AnimatedGradient<'a> {
+        AnimatedGradient {
+            gradient,
+            effect: self.effect,
+            progress: self.progress(phase),
+            easing: self.easing,
+        }
+    }
+}
+/// a gradient frozen at one phase of an `Animation`
+pub struct AnimatedGradient<'a> {
+    gradient: &'a dyn Gradient,
+    effect: AnimationEffect,
+    progress: f32,
+    easing: Easing,
+}
+impl Gradient for AnimatedGradient<'_> {
+    fn at(&self, t: f32) -> Color {
+        let (min, max) = self.gradient.domain();
+        let len = max - min;
+        // position on the rule, from 0 to 1
+        let pos =
+            if len == 0.0 { 0.0 } else { (t - min) / len };
+        match self.effect {
+            AnimationEffect::Scroll { reflect } => {
+                let t =
+                    t - self.easing.apply(self.progress)
+                        * len;
+                match reflect {
+                    // keeps the last color at the end of the domain
+                    _ if t >= min => self.gradient.at(t),
+                    true => self.gradient.reflect_at(t),
+                    false => self.gradient.repeat_at(t),
+                }
+            }
+            AnimationEffect::Pulse { dim } => {
+                // 1 at the middle of the cycle, 0 at the ends
+                let wave =
+                    1.0 - (2.0 * self.progress - 1.0).abs();
+                let brightness = 1.0
+                    - dim.clamp(0.0, 1.0)
+                        * (1.0 - self.easing.apply(wave));
+                let c = self.gradient.at(t);
+                Color::new(
+                    c.r * brightness,
+                    c.g * brightness,
+                    c.b * brightness,
+                    c.a,
+                )
+            }
+            AnimationEffect::Shimmer {
+                width,
+                strength,
+            } => {
+                let width = width.max(f32::EPSILON);
+                // starts and ends just outside the rule
+                let center =
+                    self.easing.apply(self.progress)
+                        * (1.0 + 2.0 * width)
+                        - width;
+                let k = (1.0
+                    - (pos - center).abs() / width)
+                    .max(0.0)
+                    * strength.clamp(0.0, 1.0);
+                let c = self.gradient.at(t);
+                Color::new(
+                    c.r + (1.0 - c.r) * k,
+                    c.g + (1.0 - c.g) * k,
+                    c.b + (1.0 - c.b) * k,
+                    c.a,
+                )
+            }
+        }
+    }
+    fn domain(&self) -> (f32, f32) {
+        self.gradient.domain()
+    }
+}
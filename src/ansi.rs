@@ -0,0 +1,128 @@
+//! ### Plain and ANSI text
+//! rules outside of a terminal UI, like help text, log banners and `println!` separators.
+//! the rule is rendered into a buffer the same way as in a TUI and the cells are written out,
+//! so the symbols and colors match
+//! ```rust
+//!     let rule = Rule::default()
+//!         .with_gradient(colorgrad::preset::sinebow())
+//!         .label("Logs");
+//!     println!("{}", rule.to_ansi_string(40, ColorDepth::detect()));
+//!     // ──────────────── Logs ────────────────
+//!     println!("{}", rule.to_plain_string(40));
+//! ```
+use crate::{ColorDepth, color::ANSI_16};
+use ratatui::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+/// SGR codes of the modifiers
+const MODIFIERS: [(Modifier, u8); 9] = [
+    (Modifier::BOLD, 1),
+    (Modifier::DIM, 2),
+    (Modifier::ITALIC, 3),
+    (Modifier::UNDERLINED, 4),
+    (Modifier::SLOW_BLINK, 5),
+    (Modifier::RAPID_BLINK, 6),
+    (Modifier::REVERSED, 7),
+    (Modifier::HIDDEN, 8),
+    (Modifier::CROSSED_OUT, 9),
+];
+const RESET: &str = "\x1b[0m";
+/// the text of `area` in `buf`, one line per row
+pub fn to_plain(buf: &Buffer, area: Rect) -> String {
+    rows(buf, area)
+        .iter()
+        .map(|row| {
+            row.iter().map(|cell| cell.symbol()).collect()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+/// the text of `area` in `buf` with SGR escapes for the colors and modifiers, one line per row.
+/// the colors are quantized to `depth`, `Mono` leaves them out
+pub fn to_ansi(
+    buf: &Buffer,
+    area: Rect,
+    depth: ColorDepth,
+) -> String {
+    rows(buf, area)
+        .iter()
+        .map(|row| {
+            let mut line = String::new();
+            let mut current = String::new();
+            for cell in row {
+                let sgr = sgr(cell, depth);
+                if sgr != current {
+                    if !current.is_empty() {
+                        line.push_str(RESET);
+                    }
+                    line.push_str(&sgr);
+                    current = sgr;
+                }
+                line.push_str(cell.symbol());
+            }
+            if !current.is_empty() {
+                line.push_str(RESET);
+            }
+            line
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+/// the cells of every row of `area` that are drawn.
+/// the cells under the rest of a wide glyph are left out
+pub(crate) fn rows(
+    buf: &Buffer,
+    area: Rect,
+) -> Vec<Vec<&Cell>> {
+    let area = area.intersection(buf.area);
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut row = Vec::new();
+            let mut x = area.left();
+            while x < area.right() {
+                let cell = &buf[(x, y)];
+                row.push(cell);
+                x += cell.symbol().width().max(1) as u16;
+            }
+            row
+        })
+        .collect()
+}
+/// the escape that turns on the style of `cell`, empty for the default style
+fn sgr(cell: &Cell, depth: ColorDepth) -> String {
+    let mut codes: Vec<String> = MODIFIERS
+        .iter()
+        .filter(|(m, _)| cell.modifier.contains(*m))
+        .map(|(_, code)| code.to_string())
+        .collect();
+    codes.extend(color_code(depth.quantize(cell.fg), 0));
+    codes.extend(color_code(depth.quantize(cell.bg), 10));
+    if codes.is_empty() {
+        return String::new();
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+/// the SGR code of a foreground color, `offset` by 10 for the background.
+/// `None` for the terminal's default color
+fn color_code(color: Color, offset: u8) -> Option<String> {
+    Some(match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => {
+            format!("{};2;{r};{g};{b}", 38 + offset)
+        }
+        Color::Indexed(i) => {
+            format!("{};5;{i}", 38 + offset)
+        }
+        named => {
+            let i = ANSI_16
+                .iter()
+                .position(|(c, _)| *c == named)?
+                as u8;
+            let base = if i < 8 { 30 + i } else { 82 + i };
+            (base + offset).to_string()
+        }
+    })
+}
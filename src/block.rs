@@ -0,0 +1,412 @@
+//! ### RuleBlock
+//! a `Block` replacement drawn as one continuous path, so a gradient flows
+//! clockwise around the whole border instead of restarting on every side
+//! ```rust
+//!     let block = RuleBlock::from_preset("borders::rounded")
+//!         .unwrap()
+//!         .with_gradient(colorgrad::preset::sinebow())
+//!         .title_top(Line::from("Logs").centered())
+//!         .title_left("ERR");
+//!     let inner = block.inner(area);
+//!     frame.render_widget(&block, area);
+//!     frame.render_widget(Paragraph::new(text), inner);
+//! ```
+use crate::{
+    Bg, LabelStyle, Orientation, RuleLayout, Set, presets,
+    registry,
+    text::{BgMode, Palette, with_bg},
+};
+use colorgrad::Gradient;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Margin, Position, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Padding, Widget, WidgetRef},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+/// a side of a `RuleBlock`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+/// a box drawn with four `Set`s.
+/// the corners are the start and end symbols of the top and bottom sets
+pub struct RuleBlock {
+    pub top: Set,
+    pub right: Set,
+    pub bottom: Set,
+    pub left: Set,
+    /// runs clockwise around the border, starting at the top left corner
+    pub gradient: Option<Box<dyn Gradient>>,
+    pub bg: Bg,
+    /// the style of the border under the gradient
+    pub border_style: Style,
+    /// titles drawn over the border. the alignment of the line places it on its side
+    pub titles: Vec<(Side, Line<'static>)>,
+    pub title_style: LabelStyle,
+    /// space between the border and `inner`
+    pub padding: Padding,
+}
+impl Default for RuleBlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl RuleBlock {
+    /// a plain box like `Block::bordered()`
+    pub fn new() -> Self {
+        use presets::borders::plain::*;
+        Self::from_sets(TOP, RIGHT, BOTTOM, LEFT)
+    }
+    pub fn from_sets(
+        top: Set,
+        right: Set,
+        bottom: Set,
+        left: Set,
+    ) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+            gradient: None,
+            bg: Bg::None,
+            border_style: Style::new(),
+            titles: Vec::new(),
+            title_style: LabelStyle::Gradient,
+            padding: Padding::ZERO,
+        }
+    }
+    /// a box from a border family in the registry, e.g. `"borders::double"`.
+    /// `None` when the family doesn't have all four sides
+    pub fn from_preset(family: &str) -> Option<Self> {
+        let side = |name| {
+            registry::get(&format!("{family}::{name}"))
+        };
+        Some(Self::from_sets(
+            side("TOP")?,
+            side("RIGHT")?,
+            side("BOTTOM")?,
+            side("LEFT")?,
+        ))
+    }
+    /// overrides the set of one side
+    /// ```rust
+    ///     // a double line on top of a plain box
+    ///     RuleBlock::new().side(Side::Top, presets::borders::double::TOP)
+    /// ```
+    pub fn side(mut self, side: Side, set: Set) -> Self {
+        *match side {
+            Side::Top => &mut self.top,
+            Side::Right => &mut self.right,
+            Side::Bottom => &mut self.bottom,
+            Side::Left => &mut self.left,
+        } = set;
+        self
+    }
+    pub fn with_gradient<G: Gradient + 'static>(
+        mut self,
+        gradient: G,
+    ) -> Self {
+        self.gradient = Some(Box::new(gradient));
+        self
+    }
+    pub fn bg(mut self, bg: Bg) -> Self {
+        self.bg = bg;
+        self
+    }
+    pub fn border_style<S: Into<Style>>(
+        mut self,
+        style: S,
+    ) -> Self {
+        self.border_style = style.into();
+        self
+    }
+    /// adds a title on `side`. titles on the left and right sides are read from top to bottom
+    pub fn title<L: Into<Line<'static>>>(
+        mut self,
+        side: Side,
+        title: L,
+    ) -> Self {
+        self.titles.push((side, title.into()));
+        self
+    }
+    pub fn title_top<L: Into<Line<'static>>>(
+        self,
+        title: L,
+    ) -> Self {
+        self.title(Side::Top, title)
+    }
+    pub fn title_right<L: Into<Line<'static>>>(
+        self,
+        title: L,
+    ) -> Self {
+        self.title(Side::Right, title)
+    }
+    pub fn title_bottom<L: Into<Line<'static>>>(
+        self,
+        title: L,
+    ) -> Self {
+        self.title(Side::Bottom, title)
+    }
+    pub fn title_left<L: Into<Line<'static>>>(
+        self,
+        title: L,
+    ) -> Self {
+        self.title(Side::Left, title)
+    }
+    /// sets whether the titles take part in the gradient or keep their own style
+    pub fn title_style(
+        mut self,
+        style: LabelStyle,
+    ) -> Self {
+        self.title_style = style;
+        self
+    }
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+    /// the area inside the border and padding, like `Block::inner`
+    pub fn inner(&self, area: Rect) -> Rect {
+        let area = area.inner(Margin::new(1, 1));
+        let p = self.padding;
+        let x =
+            area.x.saturating_add(p.left).min(area.right());
+        let y =
+            area.y.saturating_add(p.top).min(area.bottom());
+        Rect::new(
+            x,
+            y,
+            area.right()
+                .saturating_sub(p.right)
+                .saturating_sub(x),
+            area.bottom()
+                .saturating_sub(p.bottom)
+                .saturating_sub(y),
+        )
+    }
+    /// every cell of the border with its symbol, clockwise from the top left corner.
+    /// the cells hidden by a wide symbol have an empty one
+    pub fn path(
+        &self,
+        area: Rect,
+    ) -> Vec<(Position, String)> {
+        if area.width < 2 || area.height < 2 {
+            return Vec::new();
+        }
+        let (w, h) = (area.width, area.height);
+        let side = |set: &Set, o: Orientation, len: u16| {
+            let layout = RuleLayout::new(
+                set,
+                o.clone(),
+                Position::ORIGIN,
+                len as usize,
+                (0, 0),
+                Alignment::Center,
+                (0, 0),
+            );
+            cells(&layout.segment(set), &o, len as usize)
+        };
+        let top =
+            side(&self.top, Orientation::Horizontal, w);
+        let right =
+            side(&self.right, Orientation::Vertical, h);
+        let bottom =
+            side(&self.bottom, Orientation::Horizontal, w);
+        let left =
+            side(&self.left, Orientation::Vertical, h);
+        let (r, b) = (area.right() - 1, area.bottom() - 1);
+        let mut path =
+            Vec::with_capacity(2 * (w + h) as usize);
+        path.extend((0..w).map(|i| {
+            (
+                Position::new(area.x + i, area.y),
+                &top[i as usize],
+            )
+        }));
+        path.extend((1..h - 1).map(|i| {
+            (
+                Position::new(r, area.y + i),
+                &right[i as usize],
+            )
+        }));
+        path.extend((0..w).rev().map(|i| {
+            (
+                Position::new(area.x + i, b),
+                &bottom[i as usize],
+            )
+        }));
+        path.extend((1..h - 1).rev().map(|i| {
+            (
+                Position::new(area.x, area.y + i),
+                &left[i as usize],
+            )
+        }));
+        path.into_iter()
+            .map(|(pos, s)| (pos, s.clone()))
+            .collect()
+    }
+    /// the title cells as indices into `path`, placed between the corners of their side.
+    /// a vertical side is one cell wide, so a title on one stops at its first wide grapheme
+    fn title_cells(
+        &self,
+        area: Rect,
+    ) -> Vec<(usize, String, Style)> {
+        if area.width < 2 || area.height < 2 {
+            return Vec::new();
+        }
+        let (w, h) =
+            (area.width as usize, area.height as usize);
+        let mut out = Vec::new();
+        for (side, title) in &self.titles {
+            let o = match side {
+                Side::Top | Side::Bottom => {
+                    Orientation::Horizontal
+                }
+                Side::Left | Side::Right => {
+                    Orientation::Vertical
+                }
+            };
+            let room = match o {
+                Orientation::Horizontal => w,
+                Orientation::Vertical => h,
+            } - 2;
+            let mut graphemes = Vec::new();
+            let mut len = 0;
+            'spans: for span in title.iter() {
+                for g in span.content.graphemes(true) {
+                    let g_len = o.symbol_len(g);
+                    if len + g_len > room
+                        || (o == Orientation::Vertical
+                            && g.width() > 1)
+                    {
+                        break 'spans;
+                    }
+                    graphemes.push((
+                        g.to_string(),
+                        title.style.patch(span.style),
+                    ));
+                    len += g_len;
+                }
+            }
+            let lead = match title.alignment {
+                Some(Alignment::Center) => (room - len) / 2,
+                Some(Alignment::Right) => room - len,
+                _ => 0,
+            } + 1;
+            // `path` runs clockwise, so the bottom and left sides are walked backwards
+            let index = |cell: usize| {
+                let offset = lead + cell;
+                match side {
+                    Side::Top => offset,
+                    Side::Right => w + offset - 1,
+                    Side::Bottom => {
+                        w + h - 2 + w - 1 - offset
+                    }
+                    Side::Left => {
+                        2 * w + h - 2 + h - 2 - offset
+                    }
+                }
+            };
+            let mut cell = 0;
+            for (g, style) in graphemes {
+                let g_len = o.symbol_len(&g).max(1);
+                out.push((index(cell), g, style));
+                // the cells hidden by a wide grapheme
+                for i in 1..g_len {
+                    out.push((
+                        index(cell + i),
+                        String::new(),
+                        style,
+                    ));
+                }
+                cell += g_len;
+            }
+        }
+        out
+    }
+}
+/// the symbols of a segment, one per cell and exactly `len` cells long.
+/// a wide grapheme that would run past the end is replaced by spaces
+pub(crate) fn cells(
+    segment: &str,
+    o: &Orientation,
+    len: usize,
+) -> Vec<String> {
+    let mut out = Vec::with_capacity(len);
+    for g in segment.graphemes(true) {
+        let width = match o {
+            Orientation::Horizontal => g.width().max(1),
+            Orientation::Vertical => 1,
+        };
+        if out.len() + width > len {
+            break;
+        }
+        out.push(g.to_string());
+        out.extend((1..width).map(|_| String::new()));
+    }
+    out.resize(len, " ".to_string());
+    out
+}
+impl Widget for RuleBlock {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.render_ref(area, buf);
+    }
+}
+impl WidgetRef for RuleBlock {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let mut path = self.path(area);
+        let n = path.len();
+        let bg = BgMode::from(&self.bg);
+        let palette = self
+            .gradient
+            .as_deref()
+            .map(|g| Palette::new(g, bg, n));
+        let mut styles: Vec<Style> = (0..n)
+            .map(|i| {
+                match palette
+                    .as_ref()
+                    .and_then(|p| p.style(i, bg))
+                {
+                    Some(style) => {
+                        self.border_style.patch(style)
+                    }
+                    None => {
+                        with_bg(self.border_style, bg, None)
+                    }
+                }
+            })
+            .collect();
+        for (i, g, title_style) in self.title_cells(area) {
+            path[i].1 = g;
+            styles[i] = match self.title_style {
+                LabelStyle::Gradient => {
+                    title_style.patch(styles[i])
+                }
+                LabelStyle::Own => {
+                    styles[i].patch(title_style)
+                }
+            };
+        }
+        for ((pos, symbol), style) in
+            path.iter().zip(styles)
+        {
+            let Some(cell) = buf.cell_mut(*pos) else {
+                continue;
+            };
+            if symbol.is_empty() {
+                // hidden by the wide symbol before it
+                cell.reset();
+            } else {
+                cell.set_symbol(symbol);
+            }
+            cell.set_style(style);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! ### Palette cache
+//! the colors of a rule's gradient, kept between renders.
+//! sampling a gradient is the slowest part of drawing a rule,
+//! and the colors only change with the width of the rule, its gradients and its bg.
+//! the builders that change the gradients or the bg clear it
+use crate::text::{BgMode, Palette};
+use colorgrad::Gradient;
+use ratatui::style::Color;
+use std::{cell::RefCell, rc::Rc};
+/// where the fingerprint samples a gradient, as fractions of its domain.
+/// uneven so that gradients through the same stops with another interpolation differ
+const SAMPLES: [f32; 5] = [0.0, 0.137, 0.481, 0.829, 1.0];
+/// which gradient a palette was sampled from. the fields of a rule are public,
+/// so a gradient can be swapped without a builder and a box can reuse a freed address
+#[derive(Clone, Debug, PartialEq)]
+struct Identity {
+    ptr: *const (),
+    domain: (f32, f32),
+    samples: [[f32; 4]; SAMPLES.len()],
+}
+impl Identity {
+    fn new(gradient: &dyn Gradient) -> Self {
+        let (min, max) = gradient.domain();
+        Self {
+            ptr: gradient as *const dyn Gradient
+                as *const (),
+            domain: (min, max),
+            samples: SAMPLES.map(|t| {
+                gradient
+                    .at(min + (max - min) * t)
+                    .to_array()
+            }),
+        }
+    }
+}
+/// the bg a palette was sampled for
+#[derive(Clone, Debug, PartialEq)]
+enum BgKey {
+    None,
+    Solid(Color),
+    Same,
+    Gradient(Identity),
+}
+impl From<BgMode<'_>> for BgKey {
+    fn from(bg: BgMode) -> Self {
+        match bg {
+            BgMode::None => Self::None,
+            BgMode::Solid(c) => Self::Solid(c),
+            BgMode::Same => Self::Same,
+            BgMode::Gradient(g) => {
+                Self::Gradient(Identity::new(g))
+            }
+        }
+    }
+}
+/// what a palette was sampled for
+#[derive(Clone, Debug, PartialEq)]
+struct Key {
+    slots: usize,
+    fg: Identity,
+    bg: BgKey,
+}
+/// the last palette of a rule
+#[derive(Default)]
+pub(crate) struct PaletteCache(
+    RefCell<Option<(Key, Rc<Palette>)>>,
+);
+impl PaletteCache {
+    /// the palette of `gradient` and `bg` over `slots` slots,
+    /// sampled again only when one of them changed since the last call
+    pub(crate) fn get(
+        &self,
+        gradient: &dyn Gradient,
+        bg: BgMode,
+        slots: usize,
+    ) -> Rc<Palette> {
+        let key = Key {
+            slots,
+            fg: Identity::new(gradient),
+            bg: BgKey::from(bg),
+        };
+        if let Some((cached, palette)) = &*self.0.borrow()
+            && *cached == key
+        {
+            return Rc::clone(palette);
+        }
+        let palette =
+            Rc::new(Palette::new(gradient, bg, slots));
+        *self.0.borrow_mut() =
+            Some((key, Rc::clone(&palette)));
+        palette
+    }
+    /// forgets the last palette, after the gradients or the bg changed
+    pub(crate) fn clear(&mut self) {
+        *self.0.get_mut() = None;
+    }
+    /// the palette of the last call
+    #[cfg(test)]
+    pub(crate) fn last(&self) -> Option<Rc<Palette>> {
+        self.0.borrow().as_ref().map(|(_, p)| Rc::clone(p))
+    }
+}
@@ -0,0 +1,224 @@
+//! ### Color depth
+//! maps gradient colors onto what the terminal can show
+//! ```rust
+//!     // a 256 color gradient with ordered dithering
+//!     Rule::default()
+//!         .with_gradient(colorgrad::preset::rainbow())
+//!         .color_depth(ColorDepth::Ansi256)
+//!         .dither(true)
+//! ```
+use crate::Orientation;
+use ratatui::{style::Color, text::Span};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+/// how many colors the terminal supports
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash,
+)]
+pub enum ColorDepth {
+    /// 24-bit `Color::Rgb`, left as is
+    #[default]
+    TrueColor,
+    /// the 6x6x6 color cube and grayscale ramp of the 256 color palette (`Color::Indexed(16..=255)`)
+    Ansi256,
+    /// the 16 named colors
+    Ansi16,
+    /// no colors at all, every color becomes `Color::Reset`
+    Mono,
+}
+/// the 16 named colors with the rgb values xterm uses for them
+pub(crate) const ANSI_16: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [205, 0, 0]),
+    (Color::Green, [0, 205, 0]),
+    (Color::Yellow, [205, 205, 0]),
+    (Color::Blue, [0, 0, 238]),
+    (Color::Magenta, [205, 0, 205]),
+    (Color::Cyan, [0, 205, 205]),
+    (Color::Gray, [229, 229, 229]),
+    (Color::DarkGray, [127, 127, 127]),
+    (Color::LightRed, [255, 0, 0]),
+    (Color::LightGreen, [0, 255, 0]),
+    (Color::LightYellow, [255, 255, 0]),
+    (Color::LightBlue, [92, 92, 255]),
+    (Color::LightMagenta, [255, 0, 255]),
+    (Color::LightCyan, [0, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+/// the levels of each channel in the 256 color cube
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+/// 1D ordered dithering thresholds, repeated along the rule
+const BAYER: [f32; 4] = [0.0, 2.0, 1.0, 3.0];
+impl ColorDepth {
+    /// guesses the color depth from `COLORTERM` and `TERM`.
+    /// `NO_COLOR` and `TERM=dumb` give `Mono`
+    /// ```rust
+    ///     let rule = Rule::default().color_depth(ColorDepth::detect());
+    /// ```
+    #[cfg(feature = "crossterm")]
+    pub fn detect() -> Self {
+        use std::env::var;
+        if var("NO_COLOR").is_ok_and(|v| !v.is_empty()) {
+            return Self::Mono;
+        }
+        let from_env = Self::from_env(
+            var("COLORTERM").ok().as_deref(),
+            var("TERM").ok().as_deref(),
+        );
+        // also knows about windows terminals that support truecolor
+        match crossterm::style::available_color_count() {
+            u16::MAX if from_env != Self::Mono => {
+                Self::TrueColor
+            }
+            _ => from_env,
+        }
+    }
+    /// the color depth for the given `COLORTERM` and `TERM` values
+    pub fn from_env(
+        colorterm: Option<&str>,
+        term: Option<&str>,
+    ) -> Self {
+        let term = term.unwrap_or_default();
+        if matches!(colorterm, Some("truecolor" | "24bit"))
+            || term.contains("truecolor")
+            || term.contains("24bit")
+            || term.ends_with("-direct")
+        {
+            Self::TrueColor
+        } else if term.contains("256") {
+            Self::Ansi256
+        } else if term.is_empty() || term == "dumb" {
+            Self::Mono
+        } else {
+            Self::Ansi16
+        }
+    }
+    /// the closest color this depth can show
+    pub fn quantize(self, color: Color) -> Color {
+        self.quantize_with(color, 0.0)
+    }
+    /// like `quantize`, with ordered dithering based on the cell's position along the rule
+    pub fn quantize_dithered(
+        self,
+        color: Color,
+        cell: usize,
+    ) -> Color {
+        let threshold = (BAYER[cell % BAYER.len()] + 0.5)
+            / BAYER.len() as f32
+            - 0.5;
+        self.quantize_with(color, threshold)
+    }
+    fn quantize_with(
+        self,
+        color: Color,
+        threshold: f32,
+    ) -> Color {
+        let rgb = match (self, color) {
+            (Self::TrueColor, _) => return color,
+            (Self::Mono, _) => return Color::Reset,
+            (_, Color::Rgb(r, g, b)) => [r, g, b],
+            (Self::Ansi16, Color::Indexed(i)) => {
+                indexed_rgb(i)
+            }
+            _ => return color,
+        };
+        // roughly the distance between two palette entries
+        let step = match self {
+            Self::Ansi256 => 40.0,
+            _ => 128.0,
+        };
+        let rgb = rgb.map(|c| {
+            (c as f32 + threshold * step).clamp(0.0, 255.0)
+                as u8
+        });
+        match self {
+            Self::Ansi256 => nearest_256(rgb),
+            _ => nearest_16(rgb),
+        }
+    }
+}
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (*a as i32 - b as i32).pow(2) as u32)
+        .sum()
+}
+fn nearest_16(rgb: [u8; 3]) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, c)| distance(rgb, *c))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+fn nearest_256(rgb: [u8; 3]) -> Color {
+    let level = |c: u8| {
+        (0..CUBE.len())
+            .min_by_key(|i| {
+                (CUBE[*i] as i32 - c as i32).abs()
+            })
+            .unwrap_or_default()
+    };
+    let [r, g, b] = rgb.map(level);
+    let cube = [CUBE[r], CUBE[g], CUBE[b]];
+    // the grayscale ramp goes from 8 to 238 in steps of 10
+    let avg =
+        rgb.iter().map(|c| *c as u32).sum::<u32>() / 3;
+    let gray_i = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray_v = 8 + gray_i * 10;
+    if distance(rgb, [gray_v; 3]) < distance(rgb, cube) {
+        Color::Indexed(232 + gray_i)
+    } else {
+        Color::Indexed(16 + (36 * r + 6 * g + b) as u8)
+    }
+}
+/// the rgb value of any color but `Color::Reset`, with the xterm palette
+pub(crate) fn to_rgb(color: Color) -> Option<[u8; 3]> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some([r, g, b]),
+        Color::Indexed(i) => Some(indexed_rgb(i)),
+        named => ANSI_16
+            .iter()
+            .find(|(c, _)| *c == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+/// the rgb value of a 256 color palette entry
+fn indexed_rgb(i: u8) -> [u8; 3] {
+    match i {
+        0..16 => ANSI_16[i as usize].1,
+        16..232 => {
+            let i = i - 16;
+            [
+                CUBE[(i / 36) as usize],
+                CUBE[(i / 6 % 6) as usize],
+                CUBE[(i % 6) as usize],
+            ]
+        }
+        _ => [8 + (i - 232) * 10; 3],
+    }
+}
+/// quantizes the fg and bg of every span (one per grapheme)
+pub(crate) fn quantize_spans(
+    spans: &mut [Span],
+    depth: ColorDepth,
+    dither: bool,
+    o: &Orientation,
+) {
+    if depth == ColorDepth::TrueColor {
+        return;
+    }
+    let mut cell = 0;
+    for s in spans {
+        let q = |c: Color| match dither {
+            true => depth.quantize_dithered(c, cell),
+            false => depth.quantize(c),
+        };
+        s.style.fg = s.style.fg.map(q);
+        s.style.bg = s.style.bg.map(q);
+        cell += o.symbol_len(&s.content).max(1);
+    }
+}
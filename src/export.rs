@@ -0,0 +1,244 @@
+//! ### HTML and SVG export
+//! writes a part of a `Buffer` out with the colors of every cell, for docs and bug reports.
+//! cells without a color get those of a dark terminal
+//! ```rust
+//!     let mut buf = Buffer::empty(Rect::new(0, 0, 40, 5));
+//!     Block::bordered().render(buf.area, &mut buf);
+//!     rule.render(buf.area, &mut buf);
+//!     std::fs::write("rule.svg", export::to_svg(&buf, buf.area))?;
+//!     // a rule on its own
+//!     let buf = rule.to_buffer(40);
+//!     std::fs::write("rule.html", export::to_html(&buf, buf.area))?;
+//! ```
+use crate::{ansi, color::to_rgb};
+use ratatui::{
+    buffer::Buffer, layout::Rect, style::Modifier,
+};
+use std::fmt::Write;
+use unicode_width::UnicodeWidthStr;
+/// the text color of cells with `Color::Reset`
+pub const DEFAULT_FG: [u8; 3] = [229, 229, 229];
+/// the background of cells with `Color::Reset`
+pub const DEFAULT_BG: [u8; 3] = [0, 0, 0];
+/// the size of a cell in the SVG, in pixels
+const CELL_WIDTH: u16 = 9;
+const CELL_HEIGHT: u16 = 18;
+const FONT_SIZE: u16 = 15;
+/// cells next to each other in a row with the same style
+struct Run {
+    /// cells from the left of the area
+    x: u16,
+    /// rows from the top of the area
+    y: u16,
+    /// how many cells the text takes up
+    width: u16,
+    text: String,
+    fg: [u8; 3],
+    /// `None` for the default background
+    bg: Option<[u8; 3]>,
+    modifier: Modifier,
+}
+impl Run {
+    /// the CSS of the run, empty for the default style
+    fn css(&self) -> String {
+        let mut css = String::new();
+        if self.fg != DEFAULT_FG {
+            let _ = write!(css, "color:{};", hex(self.fg));
+        }
+        if let Some(bg) = self.bg {
+            let _ = write!(
+                css,
+                "background-color:{};",
+                hex(bg)
+            );
+        }
+        css.push_str(&self.font_css());
+        css
+    }
+    /// the CSS of the modifiers
+    fn font_css(&self) -> String {
+        let m = self.modifier;
+        let mut css = String::new();
+        if m.contains(Modifier::BOLD) {
+            css.push_str("font-weight:bold;");
+        }
+        if m.contains(Modifier::ITALIC) {
+            css.push_str("font-style:italic;");
+        }
+        if m.contains(Modifier::DIM) {
+            css.push_str("opacity:0.5;");
+        }
+        let lines: Vec<&str> = [
+            (Modifier::UNDERLINED, "underline"),
+            (Modifier::CROSSED_OUT, "line-through"),
+        ]
+        .into_iter()
+        .filter(|(modifier, _)| m.contains(*modifier))
+        .map(|(_, line)| line)
+        .collect();
+        if !lines.is_empty() {
+            let _ = write!(
+                css,
+                "text-decoration:{};",
+                lines.join(" ")
+            );
+        }
+        css
+    }
+}
+/// `area` of `buf` as a `<pre>` block, with a `<span>` for every run of cells with the same style
+pub fn to_html(buf: &Buffer, area: Rect) -> String {
+    let mut html = format!(
+        "<pre style=\"font-family:monospace;color:{};background-color:{}\">",
+        hex(DEFAULT_FG),
+        hex(DEFAULT_BG),
+    );
+    for (i, row) in rows(buf, area).iter().enumerate() {
+        if i > 0 {
+            html.push('\n');
+        }
+        for run in row {
+            let css = run.css();
+            if css.is_empty() {
+                html.push_str(&escape(&run.text));
+            } else {
+                let _ = write!(
+                    html,
+                    "<span style=\"{css}\">{}</span>",
+                    escape(&run.text)
+                );
+            }
+        }
+    }
+    html.push_str("</pre>");
+    html
+}
+/// `area` of `buf` as an SVG image, monospace text on top of the backgrounds of the cells.
+/// every run of text is stretched over its cells, so the rule lines up whatever the font
+pub fn to_svg(buf: &Buffer, area: Rect) -> String {
+    let area = area.intersection(buf.area);
+    let (width, height) = (
+        area.width as u32 * CELL_WIDTH as u32,
+        area.height as u32 * CELL_HEIGHT as u32,
+    );
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"{FONT_SIZE}\" \
+         dominant-baseline=\"central\" xml:space=\"preserve\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+        hex(DEFAULT_BG),
+    );
+    let runs: Vec<Run> =
+        rows(buf, area).into_iter().flatten().collect();
+    let rect = |run: &Run| {
+        (
+            run.x as u32 * CELL_WIDTH as u32,
+            run.y as u32 * CELL_HEIGHT as u32,
+            run.width as u32 * CELL_WIDTH as u32,
+        )
+    };
+    for run in &runs {
+        if let Some(bg) = run.bg {
+            let (x, y, w) = rect(run);
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{CELL_HEIGHT}\" fill=\"{}\"/>",
+                hex(bg)
+            );
+        }
+    }
+    for run in &runs {
+        if run.text.trim().is_empty() {
+            continue;
+        }
+        let (x, y, w) = rect(run);
+        let y = y + CELL_HEIGHT as u32 / 2;
+        let font = run.font_css();
+        let style = if font.is_empty() {
+            String::new()
+        } else {
+            format!(" style=\"{font}\"")
+        };
+        let _ = writeln!(
+            svg,
+            "<text x=\"{x}\" y=\"{y}\" fill=\"{}\" textLength=\"{w}\" lengthAdjust=\"spacingAndGlyphs\"{style}>{}</text>",
+            hex(run.fg),
+            escape(&run.text)
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+/// the runs of every row of `area`
+fn rows(buf: &Buffer, area: Rect) -> Vec<Vec<Run>> {
+    ansi::rows(buf, area)
+        .into_iter()
+        .enumerate()
+        .map(|(y, cells)| {
+            let mut runs: Vec<Run> = Vec::new();
+            let mut x = 0;
+            for cell in cells {
+                let width =
+                    cell.symbol().width().max(1) as u16;
+                let mut fg =
+                    to_rgb(cell.fg).unwrap_or(DEFAULT_FG);
+                let mut bg = to_rgb(cell.bg);
+                if cell
+                    .modifier
+                    .contains(Modifier::REVERSED)
+                {
+                    (fg, bg) = (
+                        bg.unwrap_or(DEFAULT_BG),
+                        Some(fg),
+                    );
+                }
+                if cell.modifier.contains(Modifier::HIDDEN)
+                {
+                    fg = bg.unwrap_or(DEFAULT_BG);
+                }
+                let modifier = cell.modifier
+                    - Modifier::REVERSED
+                    - Modifier::HIDDEN;
+                match runs.last_mut() {
+                    Some(run)
+                        if run.fg == fg
+                            && run.bg == bg
+                            && run.modifier == modifier =>
+                    {
+                        run.text.push_str(cell.symbol());
+                        run.width += width;
+                    }
+                    _ => runs.push(Run {
+                        x,
+                        y: y as u16,
+                        width,
+                        text: cell.symbol().to_string(),
+                        fg,
+                        bg,
+                        modifier,
+                    }),
+                }
+                x += width;
+            }
+            runs
+        })
+        .collect()
+}
+/// `#rrggbb`
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+/// escapes the characters that mean something in HTML and XML
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
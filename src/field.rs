@@ -0,0 +1,206 @@
+//! ### 2D gradients
+//! samples the gradient at the absolute position of every cell instead of along the rule,
+//! so parallel and crossing rules drawn in the same area share one color field
+//! ```rust
+//!     let field = GradientField::radial();
+//!     Rule::default()
+//!         .with_gradient(colorgrad::preset::sinebow())
+//!         .gradient_field(field)
+//!         .render(area, buf);
+//!     Rule::new_vertical()
+//!         .with_gradient(colorgrad::preset::sinebow())
+//!         .gradient_field(field)
+//!         .render(area, buf);
+//! ```
+use crate::{Bg, Orientation, text::rgb};
+use colorgrad::Gradient;
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Color, Style},
+    text::Span,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+/// the shape of the color field.
+/// positions are relative to the field's bounds, `(0, 0)` is the top left corner and `(1, 1)` the bottom right one
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FieldShape {
+    /// straight bands, running towards `angle` degrees.
+    /// 0 goes from left to right, 90 from top to bottom
+    Linear { angle: f32 },
+    /// rings around `center`, reaching the end of the gradient at the farthest corner
+    Radial { center: (f32, f32) },
+    /// a sweep around `center`, clockwise from `angle` degrees
+    Conic { center: (f32, f32), angle: f32 },
+}
+/// a gradient laid out over an area of the buffer instead of along the rule
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default)
+)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientField {
+    pub shape: FieldShape,
+    /// the area the field is stretched over.
+    /// the area the rule is rendered in when `None`
+    pub bounds: Option<Rect>,
+}
+impl Default for GradientField {
+    fn default() -> Self {
+        Self::linear(0.0)
+    }
+}
+impl GradientField {
+    fn new(shape: FieldShape) -> Self {
+        Self {
+            shape,
+            bounds: None,
+        }
+    }
+    /// straight bands towards `angle` degrees
+    pub fn linear(angle: f32) -> Self {
+        Self::new(FieldShape::Linear { angle })
+    }
+    /// rings around the middle of the bounds
+    pub fn radial() -> Self {
+        Self::new(FieldShape::Radial { center: (0.5, 0.5) })
+    }
+    /// a sweep around the middle of the bounds, starting at 12 o'clock
+    pub fn conic() -> Self {
+        Self::new(FieldShape::Conic {
+            center: (0.5, 0.5),
+            angle: -90.0,
+        })
+    }
+    /// moves the center of a radial or conic field
+    pub fn center(mut self, x: f32, y: f32) -> Self {
+        match &mut self.shape {
+            FieldShape::Radial { center }
+            | FieldShape::Conic { center, .. } => {
+                *center = (x, y)
+            }
+            FieldShape::Linear { .. } => {}
+        }
+        self
+    }
+    /// stretches the field over `bounds` instead of the area the rule is rendered in.
+    /// needed when the rules sharing the field are rendered in different areas
+    pub fn bounds(mut self, bounds: Rect) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+    /// where the cell at `pos` falls on the gradient, from 0 to 1
+    pub fn t(&self, pos: Position, bounds: Rect) -> f32 {
+        // the middle of the cell
+        let (x, y) = (
+            (pos.x as f32 + 0.5 - bounds.x as f32)
+                / bounds.width.max(1) as f32,
+            (pos.y as f32 + 0.5 - bounds.y as f32)
+                / bounds.height.max(1) as f32,
+        );
+        let t = match self.shape {
+            FieldShape::Linear { angle } => {
+                let (sin, cos) =
+                    angle.to_radians().sin_cos();
+                let dot =
+                    |x: f32, y: f32| x * cos + y * sin;
+                // the corners are at 0 and 1
+                let corners = [
+                    dot(0.0, 0.0),
+                    dot(1.0, 0.0),
+                    dot(0.0, 1.0),
+                    dot(1.0, 1.0),
+                ];
+                let min = corners
+                    .iter()
+                    .copied()
+                    .fold(f32::INFINITY, f32::min);
+                let max = corners
+                    .iter()
+                    .copied()
+                    .fold(f32::NEG_INFINITY, f32::max);
+                (dot(x, y) - min) / (max - min)
+            }
+            FieldShape::Radial { center: (cx, cy) } => {
+                let max = [
+                    (0.0, 0.0),
+                    (1.0, 0.0),
+                    (0.0, 1.0),
+                    (1.0, 1.0),
+                ]
+                .iter()
+                .map(|(x, y): &(f32, f32)| {
+                    (x - cx).hypot(y - cy)
+                })
+                .fold(0.0, f32::max);
+                (x - cx).hypot(y - cy)
+                    / max.max(f32::EPSILON)
+            }
+            FieldShape::Conic {
+                center: (cx, cy),
+                angle,
+            } => {
+                ((y - cy).atan2(x - cx).to_degrees()
+                    - angle)
+                    .rem_euclid(360.0)
+                    / 360.0
+            }
+        };
+        t.clamp(0.0, 1.0)
+    }
+}
+/// the color of `gradient` at `t` (0 to 1) of its domain
+fn sample(gradient: &dyn Gradient, t: f32) -> Color {
+    let (min, max) = gradient.domain();
+    rgb(&gradient.at(min + t * (max - min)))
+}
+/// colors every grapheme of `segment` by its position in the buffer.
+/// the segment starts at `origin`
+pub(crate) fn paint<'a>(
+    segment: &'a str,
+    o: &Orientation,
+    origin: Position,
+    gradient: &dyn Gradient,
+    bg: &Bg,
+    field: &GradientField,
+    bounds: Rect,
+) -> Vec<Span<'a>> {
+    let mut cell = 0;
+    segment
+        .graphemes(true)
+        .map(|g| {
+            let pos = match o {
+                Orientation::Horizontal => Position::new(
+                    origin.x.saturating_add(cell as u16),
+                    origin.y,
+                ),
+                Orientation::Vertical => Position::new(
+                    origin.x,
+                    origin.y.saturating_add(cell as u16),
+                ),
+            };
+            cell += o.symbol_len(g).max(1);
+            let t = field.t(pos, bounds);
+            let fg = sample(gradient, t);
+            let style = Style::new().fg(fg);
+            let style = match bg {
+                Bg::None => style,
+                Bg::Solid(c) => style.bg(*c),
+                Bg::Gradient => style.bg(fg),
+                Bg::GradientCustom(g) => {
+                    style.bg(sample(g.as_ref(), t))
+                }
+                Bg::GradientSpec(g) => {
+                    style.bg(sample(g.gradient(), t))
+                }
+            };
+            Span::styled(g, style)
+        })
+        .collect()
+}
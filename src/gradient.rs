@@ -0,0 +1,409 @@
+//! ### Gradient specs
+//! gradients described by a preset name or color stops, so rules and themes can be saved and loaded.
+//! `GradientSpec::build` turns one into a `Box<dyn Gradient>`
+use colorgrad::{
+    BasisGradient, BlendMode, CatmullRomGradient, Gradient,
+    GradientBuilder, GradientBuilderError, LinearGradient,
+    preset,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+/// ### Serializable gradient description
+/// either a `colorgrad::preset` by name or a list of color stops.
+/// unlike `Box<dyn Gradient>` it can be saved in config files
+/// ```rust
+///     // the same as colorgrad::preset::warm()
+///     let warm = GradientSpec::preset("warm");
+///     // red to blue, sampled in oklab and cut into 4 hard segments
+///     let custom = GradientSpec::stops(["#f00", "#00f"])
+///         .blend_mode(GradientBlendMode::Oklab)
+///         .sharp(4, 0.0);
+///     Rule::default().with_gradient_spec(custom)?;
+/// ```
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default)
+)]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GradientSpec {
+    /// name of a `colorgrad::preset` function, e.g. `"warm"` or `"rd_yl_bu"`.
+    /// takes priority over `stops`
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_preset")
+    )]
+    pub preset: Option<String>,
+    pub stops: Vec<ColorStop>,
+    pub blend_mode: GradientBlendMode,
+    pub interpolation: GradientInterpolation,
+    pub sharp: Option<Sharp>,
+}
+/// a single color of a custom gradient.
+/// either every stop has a position or none of them do
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorStop {
+    /// any css color (`"#ff8800"`, `"rgb(255, 136, 0)"`, `"gold"`)
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "deserialize_color")
+    )]
+    pub color: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    pub position: Option<f32>,
+}
+/// mirror of `colorgrad::BlendMode`
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Hash)]
+pub enum GradientBlendMode {
+    #[default]
+    Rgb,
+    LinearRgb,
+    Oklab,
+}
+/// how colors between the stops are interpolated
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Hash)]
+pub enum GradientInterpolation {
+    #[default]
+    Linear,
+    Basis,
+    CatmullRom,
+}
+/// turns the gradient into `segments` hard-edged colors
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sharp {
+    pub segments: u16,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub smoothness: f32,
+}
+#[derive(Clone, Debug, PartialEq)]
+pub enum GradientSpecError {
+    UnknownPreset(String),
+    /// some stops have a position and some don't
+    MixedPositions,
+    Builder(GradientBuilderError),
+}
+impl fmt::Display for GradientSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownPreset(name) => {
+                write!(
+                    f,
+                    "unknown gradient preset `{name}`"
+                )
+            }
+            Self::MixedPositions => write!(
+                f,
+                "either every color stop needs a position or none of them"
+            ),
+            Self::Builder(e) => e.fmt(f),
+        }
+    }
+}
+impl std::error::Error for GradientSpecError {}
+impl From<GradientBuilderError> for GradientSpecError {
+    fn from(e: GradientBuilderError) -> Self {
+        Self::Builder(e)
+    }
+}
+impl From<BlendMode> for GradientBlendMode {
+    fn from(mode: BlendMode) -> Self {
+        match mode {
+            BlendMode::Rgb => Self::Rgb,
+            BlendMode::LinearRgb => Self::LinearRgb,
+            BlendMode::Oklab => Self::Oklab,
+        }
+    }
+}
+impl From<GradientBlendMode> for BlendMode {
+    fn from(mode: GradientBlendMode) -> Self {
+        match mode {
+            GradientBlendMode::Rgb => Self::Rgb,
+            GradientBlendMode::LinearRgb => Self::LinearRgb,
+            GradientBlendMode::Oklab => Self::Oklab,
+        }
+    }
+}
+impl ColorStop {
+    pub fn new<S: ToString>(color: S) -> Self {
+        Self {
+            color: color.to_string(),
+            position: None,
+        }
+    }
+    pub fn at<S: ToString>(
+        color: S,
+        position: f32,
+    ) -> Self {
+        Self {
+            color: color.to_string(),
+            position: Some(position),
+        }
+    }
+}
+impl GradientSpec {
+    /// every name accepted by `GradientSpec::preset`
+    pub const PRESETS: &[&str] = &{
+        let mut names = [""; PRESET_TABLE.len()];
+        let mut i = 0;
+        while i < names.len() {
+            names[i] = PRESET_TABLE[i].0;
+            i += 1;
+        }
+        names
+    };
+    /// a `colorgrad::preset` gradient by name
+    pub fn preset<S: ToString>(name: S) -> Self {
+        Self {
+            preset: Some(name.to_string()),
+            ..Self::default()
+        }
+    }
+    /// evenly spaced colors
+    pub fn stops<S: ToString, I: IntoIterator<Item = S>>(
+        colors: I,
+    ) -> Self {
+        Self {
+            stops: colors
+                .into_iter()
+                .map(ColorStop::new)
+                .collect(),
+            ..Self::default()
+        }
+    }
+    /// colors with explicit positions
+    pub fn stops_at<
+        S: ToString,
+        I: IntoIterator<Item = (S, f32)>,
+    >(
+        stops: I,
+    ) -> Self {
+        Self {
+            stops: stops
+                .into_iter()
+                .map(|(c, p)| ColorStop::at(c, p))
+                .collect(),
+            ..Self::default()
+        }
+    }
+    pub fn blend_mode(
+        mut self,
+        mode: GradientBlendMode,
+    ) -> Self {
+        self.blend_mode = mode;
+        self
+    }
+    pub fn interpolation(
+        mut self,
+        interpolation: GradientInterpolation,
+    ) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+    pub fn sharp(
+        mut self,
+        segments: u16,
+        smoothness: f32,
+    ) -> Self {
+        self.sharp = Some(Sharp {
+            segments,
+            smoothness,
+        });
+        self
+    }
+    /// builds the described gradient
+    pub fn build(
+        &self,
+    ) -> Result<Box<dyn Gradient>, GradientSpecError> {
+        let g: Box<dyn Gradient> = match &self.preset {
+            Some(name) => build_preset(name)?,
+            None => self.build_stops()?,
+        };
+        Ok(match self.sharp {
+            Some(s) => {
+                Box::new(g.sharp(s.segments, s.smoothness))
+            }
+            None => g,
+        })
+    }
+    fn build_stops(
+        &self,
+    ) -> Result<Box<dyn Gradient>, GradientSpecError> {
+        let positions: Vec<f32> = self
+            .stops
+            .iter()
+            .filter_map(|s| s.position)
+            .collect();
+        if !positions.is_empty()
+            && positions.len() != self.stops.len()
+        {
+            return Err(GradientSpecError::MixedPositions);
+        }
+        let mut builder = GradientBuilder::new();
+        builder
+            .html_colors(
+                &self
+                    .stops
+                    .iter()
+                    .map(|s| s.color.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .mode(self.blend_mode.into());
+        if !positions.is_empty() {
+            builder.domain(&positions);
+        }
+        Ok(match self.interpolation {
+            GradientInterpolation::Linear => {
+                Box::new(builder.build::<LinearGradient>()?)
+            }
+            GradientInterpolation::Basis => {
+                Box::new(builder.build::<BasisGradient>()?)
+            }
+            GradientInterpolation::CatmullRom => Box::new(
+                builder.build::<CatmullRomGradient>()?,
+            ),
+        })
+    }
+}
+/// a gradient built from a `GradientSpec`, keeping the spec so it can be serialized.
+/// only made by `Bg::from_spec`, so the spec and the gradient always match
+pub struct SpecGradient {
+    spec: GradientSpec,
+    gradient: Box<dyn Gradient>,
+}
+impl SpecGradient {
+    pub(crate) fn new(
+        spec: GradientSpec,
+    ) -> Result<Self, GradientSpecError> {
+        Ok(Self {
+            gradient: spec.build()?,
+            spec,
+        })
+    }
+    /// what the gradient was built from
+    pub fn spec(&self) -> &GradientSpec {
+        &self.spec
+    }
+    pub fn gradient(&self) -> &dyn Gradient {
+        self.gradient.as_ref()
+    }
+}
+/// fails on an unknown name, so the parse error points at the preset and not past the spec
+#[cfg(feature = "serde")]
+fn deserialize_preset<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let preset =
+        Option::<String>::deserialize(deserializer)?;
+    match preset {
+        Some(name)
+            if !GradientSpec::PRESETS
+                .contains(&name.as_str()) =>
+        {
+            Err(serde::de::Error::custom(
+                GradientSpecError::UnknownPreset(name),
+            ))
+        }
+        preset => Ok(preset),
+    }
+}
+/// fails on a color that can't be parsed, so the parse error points at it and not past the spec
+#[cfg(feature = "serde")]
+fn deserialize_color<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    let color = String::deserialize(deserializer)?;
+    match colorgrad::Color::from_html(&color) {
+        Ok(_) => Ok(color),
+        Err(_) => Err(serde::de::Error::custom(
+            GradientSpecError::from(
+                GradientBuilderError::InvalidHtmlColors(
+                    vec![color],
+                ),
+            ),
+        )),
+    }
+}
+/// the `colorgrad::preset` functions by name
+macro_rules! preset_table {
+    ($($name:ident),* $(,)?) => {
+        const PRESET_TABLE: &[(&str, fn() -> Box<dyn Gradient>)] = &[
+            $((stringify!($name), || Box::new(preset::$name()))),*
+        ];
+    };
+}
+preset_table!(
+    sinebow,
+    turbo,
+    cividis,
+    cubehelix_default,
+    warm,
+    cool,
+    rainbow,
+    br_bg,
+    pr_gn,
+    pi_yg,
+    pu_or,
+    rd_bu,
+    rd_gy,
+    rd_yl_bu,
+    rd_yl_gn,
+    spectral,
+    blues,
+    greens,
+    greys,
+    oranges,
+    purples,
+    reds,
+    viridis,
+    inferno,
+    magma,
+    plasma,
+    bu_gn,
+    bu_pu,
+    gn_bu,
+    or_rd,
+    pu_bu_gn,
+    pu_bu,
+    pu_rd,
+    rd_pu,
+    yl_gn_bu,
+    yl_gn,
+    yl_or_br,
+    yl_or_rd,
+);
+fn build_preset(
+    name: &str,
+) -> Result<Box<dyn Gradient>, GradientSpecError> {
+    PRESET_TABLE
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, build)| build())
+        .ok_or_else(|| {
+            GradientSpecError::UnknownPreset(
+                name.to_string(),
+            )
+        })
+}
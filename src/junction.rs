@@ -0,0 +1,234 @@
+//! ### Junction merging
+//! combines box-drawing glyphs that are drawn on top of each other
+//! ```rust
+//!     assert_eq!(junction::merge("│", "─"), Some("┼"));
+//!     assert_eq!(junction::merge("║", "├"), Some("╟"));
+//! ```
+const LIGHT: u8 = 1;
+const DOUBLE: u8 = 3;
+/// the arms of every box-drawing glyph: up, right, down, left.
+/// 0 is no arm, 1 light, 2 heavy, 3 double
+const GLYPHS: &[(char, [u8; 4])] = &[
+    ('─', [0, 1, 0, 1]),
+    ('━', [0, 2, 0, 2]),
+    ('│', [1, 0, 1, 0]),
+    ('┃', [2, 0, 2, 0]),
+    ('┌', [0, 1, 1, 0]),
+    ('┍', [0, 2, 1, 0]),
+    ('┎', [0, 1, 2, 0]),
+    ('┏', [0, 2, 2, 0]),
+    ('┐', [0, 0, 1, 1]),
+    ('┑', [0, 0, 1, 2]),
+    ('┒', [0, 0, 2, 1]),
+    ('┓', [0, 0, 2, 2]),
+    ('└', [1, 1, 0, 0]),
+    ('┕', [1, 2, 0, 0]),
+    ('┖', [2, 1, 0, 0]),
+    ('┗', [2, 2, 0, 0]),
+    ('┘', [1, 0, 0, 1]),
+    ('┙', [1, 0, 0, 2]),
+    ('┚', [2, 0, 0, 1]),
+    ('┛', [2, 0, 0, 2]),
+    ('├', [1, 1, 1, 0]),
+    ('┝', [1, 2, 1, 0]),
+    ('┞', [2, 1, 1, 0]),
+    ('┟', [1, 1, 2, 0]),
+    ('┠', [2, 1, 2, 0]),
+    ('┡', [2, 2, 1, 0]),
+    ('┢', [1, 2, 2, 0]),
+    ('┣', [2, 2, 2, 0]),
+    ('┤', [1, 0, 1, 1]),
+    ('┥', [1, 0, 1, 2]),
+    ('┦', [2, 0, 1, 1]),
+    ('┧', [1, 0, 2, 1]),
+    ('┨', [2, 0, 2, 1]),
+    ('┩', [2, 0, 1, 2]),
+    ('┪', [1, 0, 2, 2]),
+    ('┫', [2, 0, 2, 2]),
+    ('┬', [0, 1, 1, 1]),
+    ('┭', [0, 1, 1, 2]),
+    ('┮', [0, 2, 1, 1]),
+    ('┯', [0, 2, 1, 2]),
+    ('┰', [0, 1, 2, 1]),
+    ('┱', [0, 1, 2, 2]),
+    ('┲', [0, 2, 2, 1]),
+    ('┳', [0, 2, 2, 2]),
+    ('┴', [1, 1, 0, 1]),
+    ('┵', [1, 1, 0, 2]),
+    ('┶', [1, 2, 0, 1]),
+    ('┷', [1, 2, 0, 2]),
+    ('┸', [2, 1, 0, 1]),
+    ('┹', [2, 1, 0, 2]),
+    ('┺', [2, 2, 0, 1]),
+    ('┻', [2, 2, 0, 2]),
+    ('┼', [1, 1, 1, 1]),
+    ('┽', [1, 1, 1, 2]),
+    ('┾', [1, 2, 1, 1]),
+    ('┿', [1, 2, 1, 2]),
+    ('╀', [2, 1, 1, 1]),
+    ('╁', [1, 1, 2, 1]),
+    ('╂', [2, 1, 2, 1]),
+    ('╃', [2, 1, 1, 2]),
+    ('╄', [2, 2, 1, 1]),
+    ('╅', [1, 1, 2, 2]),
+    ('╆', [1, 2, 2, 1]),
+    ('╇', [2, 2, 1, 2]),
+    ('╈', [1, 2, 2, 2]),
+    ('╉', [2, 1, 2, 2]),
+    ('╊', [2, 2, 2, 1]),
+    ('╋', [2, 2, 2, 2]),
+    ('═', [0, 3, 0, 3]),
+    ('║', [3, 0, 3, 0]),
+    ('╒', [0, 3, 1, 0]),
+    ('╓', [0, 1, 3, 0]),
+    ('╔', [0, 3, 3, 0]),
+    ('╕', [0, 0, 1, 3]),
+    ('╖', [0, 0, 3, 1]),
+    ('╗', [0, 0, 3, 3]),
+    ('╘', [1, 3, 0, 0]),
+    ('╙', [3, 1, 0, 0]),
+    ('╚', [3, 3, 0, 0]),
+    ('╛', [1, 0, 0, 3]),
+    ('╜', [3, 0, 0, 1]),
+    ('╝', [3, 0, 0, 3]),
+    ('╞', [1, 3, 1, 0]),
+    ('╟', [3, 1, 3, 0]),
+    ('╠', [3, 3, 3, 0]),
+    ('╡', [1, 0, 1, 3]),
+    ('╢', [3, 0, 3, 1]),
+    ('╣', [3, 0, 3, 3]),
+    ('╤', [0, 3, 1, 3]),
+    ('╥', [0, 1, 3, 1]),
+    ('╦', [0, 3, 3, 3]),
+    ('╧', [1, 3, 0, 3]),
+    ('╨', [3, 1, 0, 1]),
+    ('╩', [3, 3, 0, 3]),
+    ('╪', [1, 3, 1, 3]),
+    ('╫', [3, 1, 3, 1]),
+    ('╬', [3, 3, 3, 3]),
+    ('╴', [0, 0, 0, 1]),
+    ('╵', [1, 0, 0, 0]),
+    ('╶', [0, 1, 0, 0]),
+    ('╷', [0, 0, 1, 0]),
+    ('╸', [0, 0, 0, 2]),
+    ('╹', [2, 0, 0, 0]),
+    ('╺', [0, 2, 0, 0]),
+    ('╻', [0, 0, 2, 0]),
+    ('╼', [0, 2, 0, 1]),
+    ('╽', [1, 0, 2, 0]),
+    ('╾', [0, 1, 0, 2]),
+    ('╿', [2, 0, 1, 0]),
+    // only used when they are one of the glyphs being merged
+    ('┄', [0, 1, 0, 1]),
+    ('┅', [0, 2, 0, 2]),
+    ('┆', [1, 0, 1, 0]),
+    ('┇', [2, 0, 2, 0]),
+    ('┈', [0, 1, 0, 1]),
+    ('┉', [0, 2, 0, 2]),
+    ('┊', [1, 0, 1, 0]),
+    ('┋', [2, 0, 2, 0]),
+    ('╌', [0, 1, 0, 1]),
+    ('╍', [0, 2, 0, 2]),
+    ('╎', [1, 0, 1, 0]),
+    ('╏', [2, 0, 2, 0]),
+    ('╭', [0, 1, 1, 0]),
+    ('╮', [0, 0, 1, 1]),
+    ('╯', [1, 0, 0, 1]),
+    ('╰', [1, 1, 0, 0]),
+];
+fn arms(c: char) -> Option<[u8; 4]> {
+    GLYPHS
+        .iter()
+        .find(|(g, _)| *g == c)
+        .map(|(_, arms)| *arms)
+}
+fn glyph(arms: [u8; 4]) -> Option<char> {
+    GLYPHS.iter().find(|(_, a)| *a == arms).map(|(c, _)| *c)
+}
+/// every glyph from U+2500 to U+257F, so `merge` can hand out `&'static str`s
+const BOX_DRAWING: &str = "\
+    ─━│┃┄┅┆┇┈┉┊┋┌┍┎┏┐┑┒┓└┕┖┗┘┙┚┛├┝┞┟┠┡┢┣┤┥┦┧┨┩┪┫┬┭┮┯\
+    ┰┱┲┳┴┵┶┷┸┹┺┻┼┽┾┿╀╁╂╃╄╅╆╇╈╉╊╋╌╍╎╏═║╒╓╔╕╖╗╘╙╚╛╜╝╞╟\
+    ╠╡╢╣╤╥╦╧╨╩╪╫╬╭╮╯╰╱╲╳╴╵╶╷╸╹╺╻╼╽╾╿";
+fn as_str(c: char) -> &'static str {
+    // every glyph in the block is 3 bytes long
+    let i = (c as usize - 0x2500) * 3;
+    &BOX_DRAWING[i..i + 3]
+}
+fn single_char(symbol: &str) -> Option<char> {
+    let mut chars = symbol.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+/// the glyph for `above` drawn over `below`, e.g. `┼` for `─` over `│`.
+/// returns `None` if either of them isn't a box-drawing glyph (or `+`, `-`, `|`).
+/// light, heavy and double lines are mixed where unicode has a glyph for it,
+/// otherwise the closest one is used
+pub fn merge(
+    below: &str,
+    above: &str,
+) -> Option<&'static str> {
+    merge_trimmed(below, above, [true; 4])
+}
+/// like `merge`, but only the arms of `above` in `keep` (up, right, down, left) are added.
+/// used at the ends of a rule, so `─` starting on a `│` makes `├` instead of `┼`
+pub(crate) fn merge_trimmed(
+    below: &str,
+    above: &str,
+    keep: [bool; 4],
+) -> Option<&'static str> {
+    match (below, above) {
+        ("-", "-") => return Some("-"),
+        ("|", "|") => return Some("|"),
+        ("+" | "-" | "|", "+" | "-" | "|") => {
+            return Some("+");
+        }
+        _ => {}
+    }
+    let (below, above) =
+        (single_char(below)?, single_char(above)?);
+    let (a, b) = (arms(below)?, arms(above)?);
+    let mut merged = [0; 4];
+    for i in 0..4 {
+        merged[i] =
+            a[i].max(if keep[i] { b[i] } else { 0 });
+    }
+    // keeps dashes and rounded corners when nothing is added to them
+    if merged == b {
+        return Some(as_str(above));
+    }
+    if merged == a {
+        return Some(as_str(below));
+    }
+    glyph(merged)
+        .or_else(|| glyph(without_mixes(merged)))
+        .map(as_str)
+}
+/// unicode only mixes double lines with light ones, and only across axes.
+/// doubles spread along their axis and heavy arms become light next to them.
+/// anything that still has no glyph falls back to light lines
+fn without_mixes(mut arms: [u8; 4]) -> [u8; 4] {
+    if arms.contains(&DOUBLE) {
+        // up/down and right/left
+        for (i, j) in [(0, 2), (1, 3)] {
+            if arms[i] == DOUBLE || arms[j] == DOUBLE {
+                for k in [i, j] {
+                    if arms[k] != 0 {
+                        arms[k] = DOUBLE;
+                    }
+                }
+            }
+        }
+        for arm in &mut arms {
+            if *arm != 0 && *arm != DOUBLE {
+                *arm = LIGHT;
+            }
+        }
+        if glyph(arms).is_some() {
+            return arms;
+        }
+    }
+    arms.map(|arm| arm.min(LIGHT))
+}
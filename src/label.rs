@@ -0,0 +1,183 @@
+use crate::{Orientation, Rule};
+use ratatui::text::Span;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+/// where the label is placed on the rule
+/// ```rust
+///     // `├─ 3 errors ────────────┤`
+///     Rule::default().label("3 errors").label_position(LabelPosition::Left)
+/// ```
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Hash)]
+pub enum LabelPosition {
+    /// right after the start symbol (top for vertical rules)
+    Left,
+    /// centered between the start and end symbols
+    #[default]
+    Center,
+    /// right before the end symbol (bottom for vertical rules)
+    Right,
+    /// takes the place of the center symbol
+    ReplaceCenter,
+}
+/// how the label is colored
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Hash)]
+pub enum LabelStyle {
+    /// the rule's gradient and bg are applied on top of the label's style
+    #[default]
+    Gradient,
+    /// the label's own style is applied on top of the rule's
+    Own,
+}
+/// overlays the rule's label onto the rendered spans (one span per grapheme).
+/// `inner` is the range of cells between the start and end symbols,
+/// `center` is the range of cells taken by the center symbol
+pub(crate) fn apply_label<'a>(
+    rule: &Rule,
+    spans: Vec<Span<'a>>,
+    inner: Range<usize>,
+    center: Range<usize>,
+) -> Vec<Span<'a>> {
+    let Some(label) = &rule.label else {
+        return spans;
+    };
+    let o = &rule.orientation;
+    let (gap_1, gap_2) = (
+        rule.label_gap.0 as usize,
+        rule.label_gap.1 as usize,
+    );
+    // keeps at least one repeated symbol visible on each side
+    let available = inner.len().saturating_sub(2);
+    let budget = available.saturating_sub(gap_1 + gap_2);
+    let mut text: Vec<Span<'static>> = label
+        .spans
+        .iter()
+        .flat_map(|s| {
+            let style = label.style.patch(s.style);
+            s.content
+                .graphemes(true)
+                .map(move |g| {
+                    Span::styled(g.to_string(), style)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let text_len = |t: &[Span]| {
+        t.iter()
+            .map(|s| o.symbol_len(&s.content))
+            .sum::<usize>()
+    };
+    if text_len(&text) > budget {
+        let ellipsis_style = text
+            .last()
+            .map(|s| s.style)
+            .unwrap_or_default();
+        while !text.is_empty()
+            && text_len(&text) + 1 > budget
+        {
+            text.pop();
+        }
+        if budget > 0 {
+            text.push(Span::styled("…", ellipsis_style));
+        }
+    }
+    if text.is_empty() {
+        return spans;
+    }
+    let block_len = gap_1 + text_len(&text) + gap_2;
+    let first = inner.start + 1;
+    let last = (inner.end - 1).saturating_sub(block_len);
+    let at = match rule.label_position {
+        LabelPosition::Left => first,
+        LabelPosition::Right => last,
+        LabelPosition::Center => {
+            inner.start + (inner.len() - block_len) / 2
+        }
+        LabelPosition::ReplaceCenter => (center.start
+            + center.len() / 2)
+            .saturating_sub(block_len / 2)
+            .clamp(first, last),
+    };
+    // style of every cell before the label is put on top
+    let mut cell_styles = Vec::new();
+    for s in &spans {
+        for _ in 0..o.symbol_len(&s.content) {
+            cell_styles.push(s.style);
+        }
+    }
+    let style_at = |cell: usize| {
+        cell_styles.get(cell).copied().unwrap_or_default()
+    };
+    let mut block = Vec::with_capacity(block_len);
+    let mut cell = at;
+    for _ in 0..gap_1 {
+        block.push(Span::styled(" ", style_at(cell)));
+        cell += 1;
+    }
+    for s in text {
+        let len = o.symbol_len(&s.content);
+        let style = match rule.label_style {
+            LabelStyle::Gradient => {
+                s.style.patch(style_at(cell))
+            }
+            LabelStyle::Own => {
+                style_at(cell).patch(s.style)
+            }
+        };
+        block.push(s.style(style));
+        cell += len;
+    }
+    for _ in 0..gap_2 {
+        block.push(Span::styled(" ", style_at(cell)));
+        cell += 1;
+    }
+    overlay(spans, block, at..at + block_len, o)
+}
+/// replaces the cells in `range` with `block`.
+/// spans that are only partially covered are padded with spaces
+pub(crate) fn overlay<'a>(
+    spans: Vec<Span<'a>>,
+    block: Vec<Span<'a>>,
+    range: Range<usize>,
+    o: &Orientation,
+) -> Vec<Span<'a>> {
+    let mut out = Vec::with_capacity(spans.len());
+    let mut block = Some(block);
+    let mut cell = 0;
+    for s in spans {
+        let len = o.symbol_len(&s.content);
+        let end = cell + len;
+        if end <= range.start || cell >= range.end {
+            if cell >= range.end
+                && let Some(block) = block.take()
+            {
+                out.extend(block);
+            }
+            out.push(s);
+        } else {
+            for _ in cell..range.start.max(cell) {
+                out.push(Span::styled(" ", s.style));
+            }
+            if let Some(block) = block.take() {
+                out.extend(block);
+            }
+            for _ in range.end.min(end)..end {
+                out.push(Span::styled(" ", s.style));
+            }
+        }
+        cell = end;
+    }
+    if let Some(block) = block.take() {
+        out.extend(block);
+    }
+    out
+}
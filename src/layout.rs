@@ -0,0 +1,223 @@
+//! ### Rule layout
+//! where every part of a rule lands in the buffer.
+//! rendering, progress bars and labels use it, and so can other widgets
+//! ```rust
+//!     let layout = rule.layout(area);
+//!     // the cell of the center symbol
+//!     let center = layout.rect(RulePart::Center);
+//!     // what was clicked
+//!     let part = layout.part_at(Position::new(mouse.column, mouse.row));
+//! ```
+use crate::{Orientation, Set};
+use ratatui::layout::{Alignment, Position, Rect};
+use std::ops::Range;
+/// a part of the rule
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RulePart {
+    /// padding before the start symbol
+    PaddingBefore,
+    Start,
+    /// the run of `rep_1` between the start and center symbols
+    Rep1,
+    Center,
+    /// the run of `rep_2` between the center and end symbols
+    Rep2,
+    End,
+    /// padding after the end symbol
+    PaddingAfter,
+}
+impl RulePart {
+    /// every part, in the order they're laid out
+    pub const ALL: [RulePart; 7] = [
+        RulePart::PaddingBefore,
+        RulePart::Start,
+        RulePart::Rep1,
+        RulePart::Center,
+        RulePart::Rep2,
+        RulePart::End,
+        RulePart::PaddingAfter,
+    ];
+}
+/// the cells of every part of a rule, counted along the rule from `origin`.
+/// the parts follow each other without gaps
+#[derive(Clone, Debug, PartialEq, Hash)]
+pub struct RuleLayout {
+    pub orientation: Orientation,
+    /// the first cell of the rule, padding included
+    pub origin: Position,
+    pub padding_before: Range<usize>,
+    pub start: Range<usize>,
+    pub rep_1: Range<usize>,
+    pub center: Range<usize>,
+    pub rep_2: Range<usize>,
+    pub end: Range<usize>,
+    pub padding_after: Range<usize>,
+}
+impl RuleLayout {
+    /// lays `set` out over `len` cells from `origin`.
+    /// the center symbol stays on the midpoint of the `len` cells,
+    /// `padding` is the padding before and after the rule and
+    /// `alignment` says where it goes (`Left` is the top for vertical rules).
+    /// the repeats get `extra_rep` more symbols on top of the ones that fill the rule
+    pub fn new(
+        set: &Set,
+        orientation: Orientation,
+        origin: Position,
+        len: usize,
+        padding: (usize, usize),
+        alignment: Alignment,
+        extra_rep: (usize, usize),
+    ) -> Self {
+        let o = &orientation;
+        let (p_1, p_2) = padding;
+        let (start_len, center_len, end_len) = (
+            o.symbol_len(&set.start),
+            o.symbol_len(&set.center),
+            o.symbol_len(&set.end),
+        );
+        let center_pos =
+            (len + 1).saturating_sub(center_len) / 2;
+        let rep_1_len = center_pos
+            .saturating_sub(p_1 + start_len)
+            .saturating_add(
+                extra_rep.0 * o.symbol_len(&set.rep_1),
+            );
+        let rep_2_len = len
+            .saturating_sub(
+                center_pos + center_len + end_len + p_2,
+            )
+            .saturating_add(
+                extra_rep.1 * o.symbol_len(&set.rep_2),
+            );
+        let (before, after) = match alignment {
+            Alignment::Left => (0, p_1 + p_2),
+            Alignment::Center => (p_1, p_2),
+            Alignment::Right => (p_1 + p_2, 0),
+        };
+        let mut cell = 0;
+        let mut next = |len: usize| {
+            cell += len;
+            cell - len..cell
+        };
+        Self {
+            orientation,
+            origin,
+            padding_before: next(before),
+            start: next(start_len),
+            rep_1: next(rep_1_len),
+            center: next(center_len),
+            rep_2: next(rep_2_len),
+            end: next(end_len),
+            padding_after: next(after),
+        }
+    }
+    /// how many cells the rule takes up, padding included
+    pub fn len(&self) -> usize {
+        self.padding_after.end
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// the cells between the start and end symbols
+    pub fn track(&self) -> Range<usize> {
+        self.start.end..self.end.start
+    }
+    /// the cells of `part`
+    pub fn range(&self, part: RulePart) -> Range<usize> {
+        match part {
+            RulePart::PaddingBefore => {
+                self.padding_before.clone()
+            }
+            RulePart::Start => self.start.clone(),
+            RulePart::Rep1 => self.rep_1.clone(),
+            RulePart::Center => self.center.clone(),
+            RulePart::Rep2 => self.rep_2.clone(),
+            RulePart::End => self.end.clone(),
+            RulePart::PaddingAfter => {
+                self.padding_after.clone()
+            }
+        }
+    }
+    /// the buffer position of the `cell`th cell along the rule
+    pub fn position(&self, cell: usize) -> Position {
+        let cell = cell.min(u16::MAX as usize) as u16;
+        match self.orientation {
+            Orientation::Horizontal => Position::new(
+                self.origin.x.saturating_add(cell),
+                self.origin.y,
+            ),
+            Orientation::Vertical => Position::new(
+                self.origin.x,
+                self.origin.y.saturating_add(cell),
+            ),
+        }
+    }
+    /// the area covered by `cells`
+    pub fn cells(&self, cells: Range<usize>) -> Rect {
+        let Position { x, y } = self.position(cells.start);
+        let len = cells.len().min(u16::MAX as usize) as u16;
+        match self.orientation {
+            Orientation::Horizontal => {
+                Rect::new(x, y, len, 1)
+            }
+            Orientation::Vertical => {
+                Rect::new(x, y, 1, len)
+            }
+        }
+    }
+    /// the area covered by `part`
+    pub fn rect(&self, part: RulePart) -> Rect {
+        self.cells(self.range(part))
+    }
+    /// the whole rule, padding included
+    pub fn area(&self) -> Rect {
+        self.cells(0..self.len())
+    }
+    /// the part of the rule at `pos`, if it's on the rule
+    pub fn part_at(
+        &self,
+        pos: Position,
+    ) -> Option<RulePart> {
+        let (along, across, origin_along, origin_across) =
+            match self.orientation {
+                Orientation::Horizontal => (
+                    pos.x,
+                    pos.y,
+                    self.origin.x,
+                    self.origin.y,
+                ),
+                Orientation::Vertical => (
+                    pos.y,
+                    pos.x,
+                    self.origin.y,
+                    self.origin.x,
+                ),
+            };
+        if across != origin_across || along < origin_along {
+            return None;
+        }
+        let cell = (along - origin_along) as usize;
+        RulePart::ALL
+            .into_iter()
+            .find(|part| self.range(*part).contains(&cell))
+    }
+    /// the text of the rule, one grapheme per cell for vertical rules.
+    /// the repeats are tiled to fill their cells exactly
+    pub fn segment(&self, set: &Set) -> String {
+        let o = &self.orientation;
+        let mut ln = String::with_capacity(
+            self.len()
+                + set.start.len()
+                + set.center.len()
+                + set.end.len(),
+        );
+        ln.push_str(&" ".repeat(self.padding_before.len()));
+        ln.push_str(&set.start);
+        ln.push_str(&o.tile(&set.rep_1, self.rep_1.len()));
+        ln.push_str(&set.center);
+        ln.push_str(&o.tile(&set.rep_2, self.rep_2.len()));
+        ln.push_str(&set.end);
+        ln.push_str(&" ".repeat(self.padding_after.len()));
+        ln
+    }
+}
@@ -1,17 +1,59 @@
+pub mod animation;
+pub mod ansi;
+pub mod block;
+mod cache;
+pub mod color;
+pub mod export;
+pub mod field;
+pub mod gradient;
+pub mod junction;
+pub mod label;
+pub mod layout;
+pub mod marker;
 pub mod presets;
+mod progress;
+pub mod registry;
+#[cfg(feature = "serde")]
+mod rule_serde;
+pub mod scale;
+#[cfg(feature = "crossterm")]
+pub mod splitter;
+pub mod state;
+pub mod text;
+#[cfg(feature = "theme")]
+pub mod theme;
+pub use animation::Animation;
+pub use block::{RuleBlock, Side};
+pub use color::ColorDepth;
 use colorgrad::Gradient;
 use derive_builder::Builder;
+pub use field::{FieldShape, GradientField};
 use getset::{Getters, Setters};
+pub use gradient::{
+    GradientSpec, GradientSpecError, SpecGradient,
+};
+pub use label::{LabelPosition, LabelStyle};
+pub use layout::{RuleLayout, RulePart};
+pub use marker::{Marker, MarkerPosition, Ticks};
 use ratatui::{
-    layout::Margin,
+    layout::{Margin, Position},
     prelude::{Alignment, Buffer, Rect},
     style::Color,
-    text::Line,
+    text::{Line, Span},
     widgets::{Padding, Widget, WidgetRef},
 };
+pub use scale::Scale;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-#[cfg(feature = "all")]
+#[cfg(feature = "crossterm")]
+pub use splitter::{Splitter, SplitterState};
+pub use state::RuleState;
+use std::borrow::Cow;
+pub use text::{BgMode, GradientExt, TextGradientExt};
+#[doc(hidden)]
+pub use unicode_segmentation;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 /// ## The Rule widget
 /// ### Allows:
 ///     - Vertical alignment
@@ -21,8 +63,14 @@ use serde::{Deserialize, Serialize};
 ///     - Horizontal and vertical orientation
 ///     - Colorgrad gradients
 ///     - Start and end symbols
+///     - Labels
+///     - Thick rules
+///     - 2D gradient fields
+///     - Markers and ticks
 pub struct Rule {
     pub gradient: Option<Box<dyn Gradient>>,
+    /// what `gradient` was built from. needed to serialize the rule
+    pub gradient_spec: Option<GradientSpec>,
     pub symbol_set: Set,
     pub orientation: Orientation,
     pub padding: Padding,
@@ -32,116 +80,138 @@ pub struct Rule {
     pub extra_rep_2: usize,
     pub bg: Bg,
     pub area_margin: Margin,
+    pub label: Option<Line<'static>>,
+    pub label_position: LabelPosition,
+    pub label_gap: (u16, u16),
+    pub label_style: LabelStyle,
+    /// joins box-drawing glyphs with the ones already in the buffer
+    pub merge_junctions: bool,
+    pub color_depth: ColorDepth,
+    /// ordered dithering when `color_depth` isn't `TrueColor`
+    pub dither: bool,
+    pub animation: Option<Animation>,
+    /// where the animation is at, e.g. the seconds since the app started
+    pub phase: f32,
+    /// draws the rule as a progress bar filled up to this ratio (0 to 1)
+    pub progress: Option<f32>,
+    /// eighth blocks at the head of the progress bar instead of the center symbol
+    pub smooth_progress: bool,
+    /// how many rows the rule takes up (columns for vertical rules)
+    pub thickness: u16,
+    /// a set for each row of a thick rule, from the top (left) down.
+    /// rows without one use `symbol_set`
+    pub layers: Vec<Set>,
+    pub gradient_axis: GradientAxis,
+    /// samples the gradients at the position of every cell in the buffer instead of along the rule
+    pub gradient_field: Option<GradientField>,
+    /// glyphs put on chosen cells of the track, over the ticks
+    pub markers: Vec<Marker>,
+    pub ticks: Option<Ticks>,
+    /// the colors of `gradient` from the last render
+    cache: cache::PaletteCache,
 }
 pub enum Bg {
     None,
     Solid(Color),
     Gradient,
     GradientCustom(Box<dyn Gradient>),
+    /// a custom gradient that keeps the spec it was built from, so it can be serialized.
+    /// made with `Bg::from_spec`
+    GradientSpec(SpecGradient),
+}
+impl Bg {
+    /// builds a `Bg::GradientSpec` from a gradient spec
+    pub fn from_spec(
+        spec: GradientSpec,
+    ) -> Result<Self, GradientSpecError> {
+        Ok(Self::GradientSpec(SpecGradient::new(spec)?))
+    }
 }
+/// builds the text of a rule as a `String`.
+/// a thin wrapper around `RuleLayout::segment`
+#[deprecated(
+    note = "use `RuleLayout::new(..).segment(set)`"
+)]
 #[macro_export]
 macro_rules! create_segment {
     ($set:expr, $p_1:expr, $p_2:expr, $base_area:expr, $orientation:expr, $h_alignment:expr, $v_alignment:expr, $extra_rep_1:expr, $extra_rep_2:expr) => {{
-        let rep_count: f32 = ($base_area / 2.0) - 1.0;
-        let seg1 = $set.rep_1.to_string().repeat(
-            (rep_count.floor() as usize)
-                .saturating_sub($p_1)
-                .saturating_add($extra_rep_1),
-        );
-        let seg2 = $set.rep_2.to_string().repeat(
-            (rep_count.round() as usize)
-                .saturating_sub($p_2 + 1)
-                .saturating_add($extra_rep_2),
-        );
-        let mut ln = String::with_capacity(
-            $p_1 + $p_2
-                + 1
-                + seg1.len()
-                + 1
-                + seg2.len()
-                + 5,
-        );
-        ln.push_str(&String::from(" ").repeat(
-            match $orientation {
-                Orientation::Horizontal => {
-                    match $h_alignment {
-                        Alignment::Left => 0,
-                        Alignment::Center => $p_1,
-
-                        Alignment::Right => {
-                            $p_1.saturating_add($p_2)
-                        }
-                    }
-                }
-                Orientation::Vertical => match $v_alignment
-                {
-                    VerticalAlignment::Top => 0,
-                    VerticalAlignment::Center => $p_1,
-
-                    VerticalAlignment::Bottom => {
-                        $p_1.saturating_add($p_2)
-                    }
-                },
-            } as usize,
-        ));
-        ln.push($set.start);
-        ln.push_str(&seg1);
-        ln.push($set.center);
-        ln.push_str(&seg2);
-        ln.push($set.end);
-        ln.push_str(&String::from(" ").repeat(
-            match $orientation {
-                Orientation::Horizontal => {
-                    match $h_alignment {
-                        Alignment::Left => {
-                            $p_2.saturating_add($p_1)
-                        }
-                        Alignment::Center => $p_2,
-                        Alignment::Right => 0,
-                    }
-                }
-                Orientation::Vertical => match $v_alignment
-                {
-                    VerticalAlignment::Top => {
-                        $p_1.saturating_add($p_2)
-                    }
-                    VerticalAlignment::Center => $p_2,
-                    VerticalAlignment::Bottom => 0,
-                },
-            } as usize,
-        ));
-        ln
+        let o: $crate::Orientation = $orientation;
+        let alignment = match o {
+            $crate::Orientation::Horizontal => $h_alignment,
+            $crate::Orientation::Vertical => {
+                ratatui::layout::Alignment::from(
+                    $v_alignment,
+                )
+            }
+        };
+        $crate::RuleLayout::new(
+            &$set,
+            o,
+            ratatui::layout::Position::ORIGIN,
+            $base_area as usize,
+            ($p_1 as usize, $p_2 as usize),
+            alignment,
+            ($extra_rep_1 as usize, $extra_rep_2 as usize),
+        )
+        .segment(&$set)
     }};
 }
 /// ### Symbol set struct
+/// every slot can hold any string, including multi-character patterns,
+/// wide characters and grapheme clusters (emoji, ZWJ sequences).
+/// `rep_1` and `rep_2` are tiled to fill the rule and cut cleanly when a
+/// repeat doesn't fit.
 /// ```
 /// let set = Set {
-///     start: '+',
-///     rep_1: '─',
-///     center: '+',
-///     rep_2: '─',
-///     end: '+',
+///     start: Cow::Borrowed("<=["),
+///     rep_1: Cow::Borrowed("-="),
+///     center: Cow::Borrowed("+"),
+///     rep_2: Cow::Borrowed("=-"),
+///     end: Cow::Borrowed("]=>"),
 /// };
 /// let rule = Rule::from_set(set);
-/// // Contents would be "+───+───+"
+/// // Contents would be "<=[-=-=-+=-=-=]=>"
 /// frame.render_widget(rule, frame.area());
 /// ```
 #[cfg_attr(
     feature = "serde",
-    derive(Serialize, Deserialize)
+    derive(Serialize, Deserialize),
+    serde(default)
 )]
-#[derive(Builder, Getters, Setters, Debug, Clone)]
+#[derive(
+    Builder,
+    Getters,
+    Setters,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[builder(setter(into))]
 pub struct Set {
-    #[builder(default = "'─'")]
-    pub start: char,
-    #[builder(default = "'─'")]
-    pub end: char,
-    #[builder(default = "'─'")]
-    pub rep_1: char,
-    #[builder(default = "'─'")]
-    pub rep_2: char,
-    #[builder(default = "'─'")]
-    pub center: char,
+    #[builder(default = "Cow::Borrowed(\"─\")")]
+    pub start: Cow<'static, str>,
+    #[builder(default = "Cow::Borrowed(\"─\")")]
+    pub end: Cow<'static, str>,
+    #[builder(default = "Cow::Borrowed(\"─\")")]
+    pub rep_1: Cow<'static, str>,
+    #[builder(default = "Cow::Borrowed(\"─\")")]
+    pub rep_2: Cow<'static, str>,
+    #[builder(default = "Cow::Borrowed(\"─\")")]
+    pub center: Cow<'static, str>,
+}
+impl Default for Set {
+    /// `─────────────`
+    fn default() -> Self {
+        Self {
+            start: Cow::Borrowed("─"),
+            end: Cow::Borrowed("─"),
+            rep_1: Cow::Borrowed("─"),
+            rep_2: Cow::Borrowed("─"),
+            center: Cow::Borrowed("─"),
+        }
+    }
 }
 /// controls rule orientation
 #[cfg_attr(
@@ -153,6 +223,45 @@ pub enum Orientation {
     Vertical,
     Horizontal,
 }
+impl Orientation {
+    /// how many cells a symbol takes up along the rule.
+    /// horizontal rules use the display width (CJK and emoji are 2 cells wide),
+    /// vertical rules stack one grapheme per row
+    pub fn symbol_len(&self, symb: &str) -> usize {
+        match self {
+            Orientation::Horizontal => symb.width(),
+            Orientation::Vertical => {
+                symb.graphemes(true).count()
+            }
+        }
+    }
+    /// repeats `pattern` until it fills exactly `len` cells.
+    /// a partial repeat is cut on a grapheme boundary and
+    /// a wide grapheme that doesn't fit is replaced by spaces
+    pub fn tile(
+        &self,
+        pattern: &str,
+        len: usize,
+    ) -> String {
+        let pattern_len = self.symbol_len(pattern);
+        if pattern_len == 0 {
+            return " ".repeat(len);
+        }
+        // whole repeats always fit, only the last one is cut
+        let mut out = pattern.repeat(len / pattern_len);
+        let mut filled = len / pattern_len * pattern_len;
+        for g in pattern.graphemes(true).cycle() {
+            let g_len = self.symbol_len(g);
+            if filled + g_len > len {
+                break;
+            }
+            out.push_str(g);
+            filled += g_len;
+        }
+        out.push_str(&" ".repeat(len - filled));
+        out
+    }
+}
 /// vertical version of the Alignment enum
 #[cfg_attr(
     feature = "serde",
@@ -164,10 +273,43 @@ pub enum VerticalAlignment {
     Center,
     Bottom,
 }
+/// `Top` is `Left` and `Bottom` is `Right`, the start and end of a vertical rule
+impl From<VerticalAlignment> for Alignment {
+    fn from(alignment: VerticalAlignment) -> Self {
+        match alignment {
+            VerticalAlignment::Top => Alignment::Left,
+            VerticalAlignment::Center => Alignment::Center,
+            VerticalAlignment::Bottom => Alignment::Right,
+        }
+    }
+}
+/// which way the gradient runs on a thick rule
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Hash)]
+pub enum GradientAxis {
+    /// from the start of the rule to the end, the same on every row
+    #[default]
+    Along,
+    /// from the first row to the last, every row has one color
+    Across,
+}
+/// a gradient with the same color everywhere.
+/// colors a whole row of a rule with `GradientAxis::Across`
+struct Solid(colorgrad::Color);
+impl Gradient for Solid {
+    fn at(&self, _: f32) -> colorgrad::Color {
+        self.0.clone()
+    }
+}
 /// # Macro for generating gradient text that returns a `Vec<Span>` with the inputted gradient.
+/// a thin wrapper around `GradientExt::gradient`
 /// # Parameters
 /// 1. any type that can be converted to Line (String, Line, &str, Vec<Span>)
 /// 2. a colorgrad gradient (can be either Box<dyn Gradient> or an owned type)
+/// 3. optionally a `&Bg` for the background
 ///
 /// ```rust
 ///     let gradient_text = generate_gradient_text!("Rainbow Text", colorgrad::preset::rainbow());
@@ -177,104 +319,35 @@ pub enum VerticalAlignment {
 #[macro_export]
 macro_rules! generate_gradient_text {
     ($txt:expr, $gr:expr) => {{
-        use ratatui::prelude::{Color, Style};
-        let mut ln: Line = $txt.into();
-        ln.spans = create_raw_spans!(ln.spans[0].content);
-        let mut new_text = Vec::new();
-        for (s, c) in ln
-            .spans
-            .clone()
-            .into_iter()
-            .zip($gr.colors(ln.width()))
-        {
-            new_text.push(s.style(Style::new().fg(
-                Color::Rgb(
-                    (c.r * 255.0) as u8,
-                    (c.g * 255.0) as u8,
-                    (c.b * 255.0) as u8,
-                ),
-            )));
-        }
-        new_text
+        use $crate::text::{AsDynGradient, GradientExt};
+        $txt.gradient(
+            ($gr).as_dyn_gradient(),
+            $crate::text::BgMode::None,
+        )
+        .spans
     }};
     ($txt:expr, $gr:expr, $bgtype:expr) => {{
-        use ratatui::prelude::{Color, Style};
-        let mut ln: Line = $txt.into();
-        ln.spans = create_raw_spans!(ln.spans[0].content);
-        let mut new_text = Vec::new();
-        match $bgtype {
-            Bg::GradientCustom(grad) => {
-                for (s, (c, c2)) in
-                    ln.spans.clone().into_iter().zip(
-                        $gr.colors(ln.width())
-                            .into_iter()
-                            .zip(grad.colors(ln.width())),
-                    )
-                {
-                    new_text.push(
-                        s.style(
-                            Style::new()
-                                .fg(Color::Rgb(
-                                    (c.r * 255.0) as u8,
-                                    (c.g * 255.0) as u8,
-                                    (c.b * 255.0) as u8,
-                                ))
-                                .bg(Color::Rgb(
-                                    (c2.r * 255.0) as u8,
-                                    (c2.g * 255.0) as u8,
-                                    (c2.b * 255.0) as u8,
-                                )),
-                        ),
-                    );
-                }
-            }
-            _ => {
-                for (s, c) in ln
-                    .spans
-                    .clone()
-                    .into_iter()
-                    .zip($gr.colors(ln.width()))
-                {
-                    let c = Color::Rgb(
-                        (c.r * 255.0) as u8,
-                        (c.g * 255.0) as u8,
-                        (c.b * 255.0) as u8,
-                    );
-                    new_text.push(s.style(
-                        Style::new().fg(c).bg(
-                            match $bgtype {
-                                Bg::Solid(color) => *color,
-                                Bg::Gradient => c,
-                                _ => c,
-                            },
-                        ),
-                    ));
-                }
-            }
-        }
-        new_text
+        use $crate::text::{AsDynGradient, GradientExt};
+        $txt.gradient(
+            ($gr).as_dyn_gradient(),
+            $crate::text::BgMode::from($bgtype),
+        )
+        .spans
     }};
 }
-#[cfg(feature = "all")]
 impl Default for Rule {
     fn default() -> Self {
         Self::new()
     }
 }
-#[cfg(feature = "all")]
 impl Rule {
     /// generates a new rule that looks like `─────────────` with no gradient and no padding
     /// centered horizontally and vertically by default
     pub fn new() -> Self {
         Self {
             gradient: None,
-            symbol_set: Set {
-                start: '─',
-                end: '─',
-                center: '─',
-                rep_1: '─',
-                rep_2: '─',
-            },
+            gradient_spec: None,
+            symbol_set: Set::default(),
             padding: Padding::new(0, 0, 0, 0),
             orientation: Orientation::Horizontal,
             horizontal_alignment: Alignment::Center,
@@ -283,6 +356,24 @@ impl Rule {
             area_margin: Margin::new(1, 1),
             extra_rep_1: 0,
             extra_rep_2: 0,
+            label: None,
+            label_position: LabelPosition::Center,
+            label_gap: (1, 1),
+            label_style: LabelStyle::Gradient,
+            merge_junctions: false,
+            color_depth: ColorDepth::TrueColor,
+            dither: false,
+            animation: None,
+            phase: 0.0,
+            progress: None,
+            smooth_progress: false,
+            thickness: 1,
+            layers: Vec::new(),
+            gradient_axis: GradientAxis::Along,
+            gradient_field: None,
+            markers: Vec::new(),
+            ticks: None,
+            cache: cache::PaletteCache::default(),
         }
     }
     pub fn area_margin(mut self, margin: Margin) -> Self {
@@ -292,11 +383,13 @@ impl Rule {
     /// makes the bg solid
     pub fn bg_solid(mut self, c: Color) -> Self {
         self.bg = Bg::Solid(c);
+        self.cache.clear();
         self
     }
     /// makes the bg use the same gradient as the fg
     pub fn bg_same_gradient(mut self) -> Self {
         self.bg = Bg::Gradient;
+        self.cache.clear();
         self
     }
     pub fn extra_rep_1(mut self, rep: usize) -> Self {
@@ -322,10 +415,21 @@ impl Rule {
         g: G,
     ) -> Self {
         self.bg = Bg::GradientCustom(Box::<G>::new(g));
+        self.cache.clear();
         self
     }
+    /// makes the bg a custom gradient described by a `GradientSpec`
+    pub fn bg_gradient_spec(
+        mut self,
+        spec: GradientSpec,
+    ) -> Result<Self, GradientSpecError> {
+        self.bg = Bg::from_spec(spec)?;
+        self.cache.clear();
+        Ok(self)
+    }
     pub fn bg(mut self, bg: Bg) -> Self {
         self.bg = bg;
+        self.cache.clear();
         self
     }
     /// creates a new vertical rule
@@ -341,6 +445,19 @@ impl Rule {
     pub fn from_set(set: Set) -> Self {
         Self::new().with_set(set)
     }
+    /// creates a rule from a preset in the global `registry`,
+    /// with the orientation the preset is meant for
+    /// ```rust
+    ///     // `╔═════╗` as a horizontal rule
+    ///     let rule = Rule::from_preset("borders::double::TOP").unwrap();
+    /// ```
+    pub fn from_preset(name: &str) -> Option<Self> {
+        let info = registry::info(name)?;
+        Some(
+            Self::from_set(info.set)
+                .orientation(info.orientation),
+        )
+    }
     /// the new function and the with_gradient function combined
     /// ```rust
     ///     // displays a new rule with a rainbow gradient
@@ -361,8 +478,24 @@ impl Rule {
         gradient: G,
     ) -> Self {
         self.gradient = Some(Box::<G>::new(gradient));
+        self.gradient_spec = None;
+        self.cache.clear();
         self
     }
+    /// sets the gradient from a `GradientSpec`. unlike `with_gradient`, the rule stays serializable
+    /// ```rust
+    ///     // the same as `.with_gradient(colorgrad::preset::warm())`
+    ///     Rule::default().with_gradient_spec(GradientSpec::preset("warm"))?
+    /// ```
+    pub fn with_gradient_spec(
+        mut self,
+        spec: GradientSpec,
+    ) -> Result<Self, GradientSpecError> {
+        self.gradient = Some(spec.build()?);
+        self.gradient_spec = Some(spec);
+        self.cache.clear();
+        Ok(self)
+    }
     /// sets the horizontal padding
     pub fn horizontal_padding(
         mut self,
@@ -406,12 +539,7 @@ impl Rule {
     }
     /// Sets the end, start, right, center, and left symbols from the Set struct
     pub fn with_set(mut self, set: Set) -> Self {
-        self = self
-            .end(set.end)
-            .start(set.start)
-            .rep_2(set.rep_2)
-            .rep_1(set.rep_1)
-            .center(set.center);
+        self.symbol_set = set;
         self
     }
     /// makes the rule horizontal instead of vertical. Horizontal by default
@@ -429,8 +557,9 @@ impl Rule {
     ///     Rule::default().rep_2('-')
     /// ```
     /// `+=====+-----+`
-    pub fn rep_2(mut self, symb: char) -> Self {
-        self.symbol_set.rep_2 = symb;
+    pub fn rep_2(mut self, symb: impl ToString) -> Self {
+        self.symbol_set.rep_2 =
+            Cow::Owned(symb.to_string());
         self
     }
     /// repeated symbol for left side
@@ -438,8 +567,9 @@ impl Rule {
     ///     Rule::default().rep_1('-')
     /// ```
     /// `+-----+=====+`
-    pub fn rep_1(mut self, symb: char) -> Self {
-        self.symbol_set.rep_1 = symb;
+    pub fn rep_1(mut self, symb: impl ToString) -> Self {
+        self.symbol_set.rep_1 =
+            Cow::Owned(symb.to_string());
         self
     }
     /// first symbol
@@ -447,8 +577,9 @@ impl Rule {
     ///     Rule::default().start('%')
     /// ```
     /// `%=====+=====+`
-    pub fn start(mut self, symb: char) -> Self {
-        self.symbol_set.start = symb;
+    pub fn start(mut self, symb: impl ToString) -> Self {
+        self.symbol_set.start =
+            Cow::Owned(symb.to_string());
         self
     }
     /// last symbol
@@ -456,8 +587,8 @@ impl Rule {
     ///     Rule::default().end('%');
     ///```   
     /// `+=====+=====%`
-    pub fn end(mut self, symb: char) -> Self {
-        self.symbol_set.end = symb;
+    pub fn end(mut self, symb: impl ToString) -> Self {
+        self.symbol_set.end = Cow::Owned(symb.to_string());
         self
     }
     /// center symbol  
@@ -465,13 +596,18 @@ impl Rule {
     ///     Rule::default().center('%')
     ///```
     /// `+=====%=====+`
-    pub fn center(mut self, symb: char) -> Self {
-        self.symbol_set.center = symb;
+    pub fn center(mut self, symb: impl ToString) -> Self {
+        self.symbol_set.center =
+            Cow::Owned(symb.to_string());
         self
     }
     /// the rep_1 and the rep_2 functions in one
-    pub fn main_symbol(mut self, symb: char) -> Self {
-        self = self.rep_1(symb).rep_2(symb);
+    pub fn main_symbol(
+        mut self,
+        symb: impl ToString,
+    ) -> Self {
+        let symb = symb.to_string();
+        self = self.rep_1(&symb).rep_2(symb);
         self
     }
     pub fn padding(mut self, padding: Padding) -> Self {
@@ -512,8 +648,211 @@ impl Rule {
         self.horizontal_alignment = alignment;
         self
     }
+    /// text drawn inside the rule. truncated with `…` if the rule is too short
+    /// ```rust
+    ///     // `───────── Logs ─────────`
+    ///     Rule::default().label("Logs")
+    /// ```
+    pub fn label<L: Into<Line<'static>>>(
+        mut self,
+        label: L,
+    ) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+    /// sets where the label is placed. centered by default
+    /// ```rust
+    ///     // `├─ 3 errors ────────────┤`
+    ///     Rule::default().label("3 errors").label_position(LabelPosition::Left)
+    /// ```
+    pub fn label_position(
+        mut self,
+        position: LabelPosition,
+    ) -> Self {
+        self.label_position = position;
+        self
+    }
+    /// sets the number of spaces before and after the label. `(1, 1)` by default
+    pub fn label_gap(
+        mut self,
+        before: u16,
+        after: u16,
+    ) -> Self {
+        self.label_gap = (before, after);
+        self
+    }
+    /// sets whether the label takes part in the gradient or keeps its own style
+    pub fn label_style(
+        mut self,
+        style: LabelStyle,
+    ) -> Self {
+        self.label_style = style;
+        self
+    }
+    /// joins the rule with box-drawing glyphs already in the buffer instead of overwriting them,
+    /// e.g. `┼` where it crosses a vertical rule or `├` where it meets a `Block` border
+    /// ```rust
+    ///     Rule::default().merge_junctions(true)
+    /// ```
+    pub fn merge_junctions(mut self, merge: bool) -> Self {
+        self.merge_junctions = merge;
+        self
+    }
+    /// sets how many colors the terminal can show. gradients are mapped to the closest ones.
+    /// `TrueColor` by default
+    /// ```rust
+    ///     Rule::default().color_depth(ColorDepth::detect())
+    /// ```
+    pub fn color_depth(
+        mut self,
+        depth: ColorDepth,
+    ) -> Self {
+        self.color_depth = depth;
+        self
+    }
+    /// dithers the gradient along the rule when the color depth is reduced,
+    /// which hides the banding of the smaller palette
+    pub fn dither(mut self, dither: bool) -> Self {
+        self.dither = dither;
+        self
+    }
+    /// animates the gradient. custom bg gradients stay still
+    /// ```rust
+    ///     // a rainbow flowing from right to left, once every 2 seconds
+    ///     Rule::default()
+    ///         .with_gradient(colorgrad::preset::rainbow())
+    ///         .animation(
+    ///             Animation::scroll()
+    ///                 .speed(0.5)
+    ///                 .direction(AnimationDirection::Backward),
+    ///         )
+    ///         .phase(elapsed_secs)
+    /// ```
+    pub fn animation(
+        mut self,
+        animation: Animation,
+    ) -> Self {
+        self.animation = Some(animation);
+        self
+    }
+    /// sets the phase of the animation
+    pub fn phase(mut self, phase: f32) -> Self {
+        self.phase = phase;
+        self
+    }
+    /// turns the rule into a progress bar filled up to `ratio` (0 to 1).
+    /// `rep_1` is the filled part, `center` the head and `rep_2` the rest.
+    /// the gradient only colors the filled part.
+    /// vertical bars fill from the bottom up
+    /// ```rust
+    ///     // `├━━━━━━╸──────┤`
+    ///     Rule::default()
+    ///         .start("├")
+    ///         .end("┤")
+    ///         .rep_1("━")
+    ///         .center("╸")
+    ///         .progress(0.5)
+    /// ```
+    pub fn progress(mut self, ratio: f32) -> Self {
+        self.progress = Some(ratio);
+        self
+    }
+    /// ends the filled part of the progress bar with eighth blocks (`▏` to `▉`, `▁` to `▇`)
+    /// for sub-cell precision. works best with `█` as `rep_1`
+    /// ```rust
+    ///     Rule::from_set(presets::progress::BLOCK)
+    ///         .progress(0.33)
+    ///         .smooth_progress(true)
+    /// ```
+    pub fn smooth_progress(mut self, smooth: bool) -> Self {
+        self.smooth_progress = smooth;
+        self
+    }
+    /// makes the rule `thickness` rows tall (columns wide for vertical rules).
+    /// every row repeats the symbol set unless `layers` gives it its own.
+    /// the band grows away from the side the rule is aligned to
+    /// ```rust
+    ///     Rule::from_set(presets::bands::UPPER)
+    ///         .thickness(3)
+    /// ```
+    pub fn thickness(mut self, thickness: u16) -> Self {
+        self.thickness = thickness;
+        self
+    }
+    /// gives every row of the rule its own set and makes it as thick as there are sets
+    /// ```rust
+    ///     // `▀▀▀▀▀▀`
+    ///     // `▄▄▄▄▄▄`
+    ///     Rule::default().layers(vec![
+    ///         presets::bands::UPPER,
+    ///         presets::bands::LOWER,
+    ///     ])
+    /// ```
+    pub fn layers(mut self, layers: Vec<Set>) -> Self {
+        self.thickness = layers.len().max(1) as u16;
+        self.layers = layers;
+        self
+    }
+    /// sets whether the gradient runs along the rule or across its thickness
+    /// ```rust
+    ///     // a red row on top of a blue one
+    ///     Rule::from_set(presets::borders::double::TOP)
+    ///         .with_gradient(colorgrad::preset::rd_bu())
+    ///         .thickness(2)
+    ///         .gradient_axis(GradientAxis::Across)
+    /// ```
+    pub fn gradient_axis(
+        mut self,
+        axis: GradientAxis,
+    ) -> Self {
+        self.gradient_axis = axis;
+        self
+    }
+    /// lays the gradient (and a custom bg gradient) out over the area instead of along the rule.
+    /// rules with the same field share its colors where they cross or run side by side.
+    /// takes priority over `gradient_axis`
+    /// ```rust
+    ///     // a diagonal rainbow over the whole frame
+    ///     Rule::default()
+    ///         .with_gradient(colorgrad::preset::rainbow())
+    ///         .gradient_field(GradientField::linear(45.0))
+    /// ```
+    pub fn gradient_field(
+        mut self,
+        field: GradientField,
+    ) -> Self {
+        self.gradient_field = Some(field);
+        self
+    }
+    /// adds a marker. markers go on top of the ticks and under the label.
+    /// a set whose center is its repeated symbol and a marker moves the center anywhere
+    /// ```rust
+    ///     // `├─────┼──────────────┤`
+    ///     Rule::default()
+    ///         .start("├")
+    ///         .end("┤")
+    ///         .marker(Marker::ratio(0.3, "┼"))
+    /// ```
+    pub fn marker(mut self, marker: Marker) -> Self {
+        self.markers.push(marker);
+        self
+    }
+    pub fn markers(mut self, markers: Vec<Marker>) -> Self {
+        self.markers = markers;
+        self
+    }
+    /// evenly spaced ticks along the track, for scales and timelines
+    /// ```rust
+    ///     // `─┼─┴─┴─┴─┴─┼─┴─┴─┴─┴─`
+    ///     Rule::default()
+    ///         .area_margin(Margin::new(0, 0))
+    ///         .ticks(Ticks::new(2, "┴").major(10, "┼"))
+    /// ```
+    pub fn ticks(mut self, ticks: Ticks) -> Self {
+        self.ticks = Some(ticks);
+        self
+    }
 }
-#[cfg(feature = "all")]
 impl Widget for Rule {
     fn render(self, area_old: Rect, buf: &mut Buffer) {
         self.render_ref(area_old, buf);
@@ -764,178 +1103,2526 @@ mod tests {
             .render(buffer.area, &mut buffer);
         assert_eq!(buffer, expected);
     }
-}
-pub mod macros {
-    #[cfg(feature = "utils")]
-    #[macro_export]
-    macro_rules! gen_main {
-        () => {
-            fn main() -> io::Result<()> {
-                let mut terminal = ratatui::init();
-                let app_result = run(&mut terminal);
-                ratatui::restore();
-                app_result
-            }
-        };
+    #[test]
+    pub fn test_multi_char_set() {
+        use super::*;
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 21, 3));
+        Rule::from_set(
+            SetBuilder::default()
+                .start("<=[")
+                .rep_1("-=")
+                .center("+")
+                .rep_2("=-")
+                .end("]=>")
+                .build()
+                .unwrap(),
+        )
+        .render(buffer.area, &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "                     ",
+            " <=[-=-=-=+=-=-=-]=> ",
+            "                     ",
+        ]);
+        assert_eq!(buffer, expected);
+        buffer = Buffer::empty(Rect::new(0, 0, 21, 3));
+        Rule::new()
+            .start("[")
+            .rep_1("世")
+            .center("🏳️‍🌈")
+            .rep_2("ab")
+            .end("]")
+            .render(buffer.area, &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "                     ",
+            " [世世世世🏳️‍🌈abababa] ",
+            "                     ",
+        ]);
+        assert_eq!(buffer, expected);
     }
-    #[cfg(feature = "utils")]
-    #[macro_export]
-    macro_rules! gen_example_code {
-        ($fun:item) => {
-            tui_rule::gen_use!();
-            tui_rule::gen_run!($fun);
-            tui_rule::gen_main!();
+    #[test]
+    pub fn test_label() {
+        use super::*;
+        use ratatui::style::{Modifier, Stylize};
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 26, 3));
+        let rule = || {
+            Rule::from_set(presets::test_sets::HORIZONTAL)
+                .start("├")
+                .end("┤")
         };
+        rule()
+            .label("Logs")
+            .render(buffer.area, &mut buffer);
+        rule()
+            .label("3 errors")
+            .label_position(LabelPosition::Left)
+            .area_margin(Margin::new(1, 0))
+            .vertical_alignment(VerticalAlignment::Top)
+            .render(buffer.area, &mut buffer);
+        rule()
+            .label("a very long label that gets cut")
+            .label_position(LabelPosition::Right)
+            .label_gap(0, 1)
+            .area_margin(Margin::new(1, 0))
+            .vertical_alignment(VerticalAlignment::Bottom)
+            .render(buffer.area, &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            " ├─ 3 errors +──────────┤ ",
+            " ├──────── Logs ────────┤ ",
+            " ├─a very long label … ─┤ ",
+        ]);
+        assert_eq!(buffer, expected);
+        buffer = Buffer::empty(Rect::new(0, 0, 16, 3));
+        Rule::from_set(presets::test_sets::HORIZONTAL)
+            .label(Line::from("own".bold()))
+            .label_style(LabelStyle::Own)
+            .label_position(LabelPosition::ReplaceCenter)
+            .bg_solid(Color::Blue)
+            .with_gradient(colorgrad::preset::warm())
+            .render(buffer.area, &mut buffer);
+        let cell = &buffer[(7, 1)];
+        assert_eq!(cell.symbol(), "o");
+        assert_eq!(cell.bg, Color::Blue);
+        assert!(cell.modifier.contains(Modifier::BOLD));
+        // the gap keeps the rule's bg
+        assert_eq!(buffer[(6, 1)].bg, Color::Blue);
     }
-    #[cfg(feature = "utils")]
-    #[macro_export]
-    macro_rules! gen_run {
-        ($fun:item) => {
-            $fun
-        };
+    #[test]
+    pub fn test_registry() {
+        use super::*;
+        use registry::Registry;
+        let builtin = Registry::builtin();
+        let info =
+            builtin.info("borders::double::TOP").unwrap();
+        assert_eq!(info.category(), "borders::double");
+        assert_eq!(
+            info.orientation,
+            Orientation::Horizontal
+        );
+        assert_eq!(info.preview(7), "╔═════╗");
+        assert_eq!(
+            builtin
+                .info("borders::plain::LEFT")
+                .unwrap()
+                .preview(3),
+            "┌\n│\n└"
+        );
+        assert_eq!(builtin.category("borders").count(), 84);
+        assert!(builtin.get("borders::double").is_none());
+        registry::register(
+            "test::arrows",
+            Orientation::Horizontal,
+            SetBuilder::default()
+                .start("<")
+                .end(">")
+                .build()
+                .unwrap(),
+        );
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 9, 3));
+        Rule::from_preset("test::arrows")
+            .unwrap()
+            .render(buffer.area, &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "         ",
+            " <─────> ",
+            "         ",
+        ]);
+        assert_eq!(buffer, expected);
+        assert!(Rule::from_preset("test::nope").is_none());
+        #[cfg(feature = "theme")]
+        {
+            use theme::{Theme, ThemeFormat};
+            let theme = Theme::parse(
+                "[a]\nsymbol_set = \"test::arrows\"\n",
+                ThemeFormat::Toml,
+            )
+            .unwrap();
+            assert_eq!(theme["a"].symbol_set.start, "<");
+            assert!(
+                Theme::parse(
+                    "[a]\nsymbol_set = \"test::nope\"\n",
+                    ThemeFormat::Toml,
+                )
+                .is_err()
+            );
+        }
     }
-    #[cfg(feature = "utils")]
-    #[macro_export]
-    macro_rules! gen_use {
-        () => {
-            use colorgrad::Gradient;
-            use crossterm::event::{
-                self, Event, KeyCode, KeyEvent,
-                KeyEventKind,
-            };
-            use ratatui::{
-                buffer::Buffer,
-                layout::Rect,
-                prelude::{Alignment, Color, Style},
-                text::Line,
-                widgets::{Block, Widget},
-                DefaultTerminal, Frame,
-            };
-            use std::{io, rc::Rc};
-            use tui_rule::*;
-        };
+    #[test]
+    pub fn test_border_families() {
+        use super::*;
+        // every family drawn as a box split by a cross, with a tee on each axis
+        let layout = [
+            ("TOP", Rect::new(0, 0, 13, 1)),
+            ("BOTTOM", Rect::new(0, 6, 13, 1)),
+            ("LEFT", Rect::new(0, 0, 1, 7)),
+            ("RIGHT", Rect::new(12, 0, 1, 7)),
+            ("HORIZONTAL_CROSS", Rect::new(0, 3, 13, 1)),
+            ("VERTICAL_CROSS", Rect::new(6, 0, 1, 7)),
+            ("HORIZONTAL_TEE", Rect::new(0, 5, 7, 1)),
+            ("VERTICAL_TEE", Rect::new(9, 3, 1, 4)),
+        ];
+        #[rustfmt::skip]
+        let families = [
+            (
+                "plain",
+                [
+                    "┌─────┬─────┐",
+                    "│     │     │",
+                    "│     │     │",
+                    "├─────┼──┬──┤",
+                    "│     │  │  │",
+                    "├─────┤  │  │",
+                    "└─────┴──┴──┘",
+                ],
+            ),
+            (
+                "double",
+                [
+                    "╔═════╦═════╗",
+                    "║     ║     ║",
+                    "║     ║     ║",
+                    "╠═════╬══╦══╣",
+                    "║     ║  ║  ║",
+                    "╠═════╣  ║  ║",
+                    "╚═════╩══╩══╝",
+                ],
+            ),
+            (
+                "rounded",
+                [
+                    "╭─────┬─────╮",
+                    "│     │     │",
+                    "│     │     │",
+                    "├─────┼──┬──┤",
+                    "│     │  │  │",
+                    "├─────┤  │  │",
+                    "╰─────┴──┴──╯",
+                ],
+            ),
+            (
+                "thick",
+                [
+                    "┏━━━━━┳━━━━━┓",
+                    "┃     ┃     ┃",
+                    "┃     ┃     ┃",
+                    "┣━━━━━╋━━┳━━┫",
+                    "┃     ┃  ┃  ┃",
+                    "┣━━━━━┫  ┃  ┃",
+                    "┗━━━━━┻━━┻━━┛",
+                ],
+            ),
+            (
+                "dashed",
+                [
+                    "┌┄┄┄┄┄┬┄┄┄┄┄┐",
+                    "┆     ┆     ┆",
+                    "┆     ┆     ┆",
+                    "├┄┄┄┄┄┼┄┄┬┄┄┤",
+                    "┆     ┆  ┆  ┆",
+                    "├┄┄┄┄┄┤  ┆  ┆",
+                    "└┄┄┄┄┄┴┄┄┴┄┄┘",
+                ],
+            ),
+            (
+                "dotted",
+                [
+                    "┌┈┈┈┈┈┬┈┈┈┈┈┐",
+                    "┊     ┊     ┊",
+                    "┊     ┊     ┊",
+                    "├┈┈┈┈┈┼┈┈┬┈┈┤",
+                    "┊     ┊  ┊  ┊",
+                    "├┈┈┈┈┈┤  ┊  ┊",
+                    "└┈┈┈┈┈┴┈┈┴┈┈┘",
+                ],
+            ),
+            (
+                "heavy_dashed",
+                [
+                    "┏┅┅┅┅┅┳┅┅┅┅┅┓",
+                    "┇     ┇     ┇",
+                    "┇     ┇     ┇",
+                    "┣┅┅┅┅┅╋┅┅┳┅┅┫",
+                    "┇     ┇  ┇  ┇",
+                    "┣┅┅┅┅┅┫  ┇  ┇",
+                    "┗┅┅┅┅┅┻┅┅┻┅┅┛",
+                ],
+            ),
+            (
+                "heavy_horizontal",
+                [
+                    "┍━━━━━┯━━━━━┑",
+                    "│     │     │",
+                    "│     │     │",
+                    "┝━━━━━┿━━┯━━┥",
+                    "│     │  │  │",
+                    "┝━━━━━┥  │  │",
+                    "┕━━━━━┷━━┷━━┙",
+                ],
+            ),
+            (
+                "heavy_vertical",
+                [
+                    "┎─────┰─────┒",
+                    "┃     ┃     ┃",
+                    "┃     ┃     ┃",
+                    "┠─────╂──┰──┨",
+                    "┃     ┃  ┃  ┃",
+                    "┠─────┨  ┃  ┃",
+                    "┖─────┸──┸──┚",
+                ],
+            ),
+            (
+                "quadrant",
+                [
+                    "▛▀▀▀▀▀▀▀▀▀▀▀▜",
+                    "▌           ▐",
+                    "▌           ▐",
+                    "▌           ▐",
+                    "▌           ▐",
+                    "▌           ▐",
+                    "▙▄▄▄▄▄▄▄▄▄▄▄▟",
+                ],
+            ),
+            (
+                "ascii",
+                [
+                    "+-----+-----+",
+                    "|     |     |",
+                    "|     |     |",
+                    "+-----+--+--+",
+                    "|     |  |  |",
+                    "+-----+  |  |",
+                    "+-----+--+--+",
+                ],
+            ),
+        ];
+        for (family, lines) in families {
+            let mut buffer =
+                Buffer::empty(Rect::new(0, 0, 13, 7));
+            for (name, area) in layout {
+                // quadrant has no tees
+                let Some(rule) = Rule::from_preset(
+                    &format!("borders::{family}::{name}"),
+                ) else {
+                    continue;
+                };
+                rule.area_margin(Margin::new(0, 0))
+                    .vertical_alignment(
+                        VerticalAlignment::Top,
+                    )
+                    .horizontal_alignment(Alignment::Left)
+                    .render(area, &mut buffer);
+            }
+            assert_eq!(
+                buffer,
+                Buffer::with_lines(lines),
+                "{family}"
+            );
+        }
     }
-    #[macro_export]
-    macro_rules! create_raw_spans {
-        ($string:expr) => {
-            $string
-                .chars()
-                .map(String::from)
-                .map(ratatui::text::Span::from)
-                .collect::<Vec<ratatui::text::Span>>()
+    #[test]
+    pub fn test_junctions() {
+        use super::*;
+        use ratatui::widgets::BorderType;
+        let rules = |buffer: &mut Buffer,
+                     h: &str,
+                     v: &str| {
+            let rule = |symbol: &str| {
+                Rule::new()
+                    .main_symbol(symbol)
+                    .start(symbol)
+                    .center(symbol)
+                    .end(symbol)
+                    .area_margin(Margin::new(0, 0))
+                    .vertical_alignment(
+                        VerticalAlignment::Top,
+                    )
+                    .horizontal_alignment(Alignment::Left)
+                    .merge_junctions(true)
+            };
+            rule(h).render(Rect::new(0, 2, 9, 1), buffer);
+            rule(v)
+                .vertical()
+                .render(Rect::new(4, 0, 1, 5), buffer);
+        };
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 9, 5));
+        Block::bordered().render(buffer.area, &mut buffer);
+        rules(&mut buffer, "─", "│");
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "┌───┬───┐",
+            "│   │   │",
+            "├───┼───┤",
+            "│   │   │",
+            "└───┴───┘",
+        ]);
+        assert_eq!(buffer, expected);
+        buffer = Buffer::empty(Rect::new(0, 0, 9, 5));
+        Block::bordered()
+            .border_type(BorderType::Double)
+            .render(buffer.area, &mut buffer);
+        rules(&mut buffer, "─", "│");
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "╔═══╤═══╗",
+            "║   │   ║",
+            "╟───┼───╢",
+            "║   │   ║",
+            "╚═══╧═══╝",
+        ]);
+        assert_eq!(buffer, expected);
+        buffer = Buffer::empty(Rect::new(0, 0, 9, 5));
+        Block::bordered()
+            .border_type(BorderType::Thick)
+            .render(buffer.area, &mut buffer);
+        rules(&mut buffer, "┄", "┆");
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "┏━━━┯━━━┓",
+            "┃   ┆   ┃",
+            "┠┄┄┄┼┄┄┄┨",
+            "┃   ┆   ┃",
+            "┗━━━┷━━━┛",
+        ]);
+        assert_eq!(buffer, expected);
+        // heavy and double can't be mixed, the heavy line becomes light
+        assert_eq!(junction::merge("═", "┃"), Some("╪"));
+        assert_eq!(junction::merge("╭", "│"), Some("├"));
+        assert_eq!(junction::merge("-", "|"), Some("+"));
+        assert_eq!(junction::merge("│", "x"), None);
+        // without merging the rule overwrites the border
+        buffer = Buffer::empty(Rect::new(0, 0, 9, 3));
+        Block::bordered().render(buffer.area, &mut buffer);
+        Rule::new()
+            .area_margin(Margin::new(0, 0))
+            .vertical_alignment(VerticalAlignment::Top)
+            .render(Rect::new(0, 1, 9, 1), &mut buffer);
+        assert_eq!(buffer[(0, 1)].symbol(), "─");
+    }
+    #[test]
+    pub fn test_color_depth() {
+        use super::*;
+        assert_eq!(
+            ColorDepth::Ansi256
+                .quantize(Color::Rgb(255, 0, 0)),
+            Color::Indexed(196)
+        );
+        assert_eq!(
+            ColorDepth::Ansi256
+                .quantize(Color::Rgb(128, 128, 128)),
+            Color::Indexed(244)
+        );
+        assert_eq!(
+            ColorDepth::Ansi16
+                .quantize(Color::Rgb(250, 10, 10)),
+            Color::LightRed
+        );
+        assert_eq!(
+            ColorDepth::Ansi16.quantize(Color::Indexed(21)),
+            Color::Blue
+        );
+        assert_eq!(
+            ColorDepth::Mono.quantize(Color::Rgb(1, 2, 3)),
+            Color::Reset
+        );
+        for (colorterm, term, depth) in [
+            (
+                Some("truecolor"),
+                Some("xterm"),
+                ColorDepth::TrueColor,
+            ),
+            (
+                None,
+                Some("xterm-direct"),
+                ColorDepth::TrueColor,
+            ),
+            (
+                None,
+                Some("screen-256color"),
+                ColorDepth::Ansi256,
+            ),
+            (None, Some("linux"), ColorDepth::Ansi16),
+            (None, Some("dumb"), ColorDepth::Mono),
+        ] {
+            assert_eq!(
+                ColorDepth::from_env(colorterm, term),
+                depth
+            );
+        }
+        let render = |depth: ColorDepth, dither: bool| {
+            let mut buffer =
+                Buffer::empty(Rect::new(0, 0, 32, 3));
+            Rule::new()
+                .with_gradient(colorgrad::preset::rainbow())
+                .bg_solid(Color::Rgb(0, 0, 0))
+                .color_depth(depth)
+                .dither(dither)
+                .render(buffer.area, &mut buffer);
+            (1..31)
+                .map(|x| {
+                    (buffer[(x, 1)].fg, buffer[(x, 1)].bg)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert!(
+            render(ColorDepth::TrueColor, true).iter().all(
+                |(fg, _)| matches!(fg, Color::Rgb(..))
+            )
+        );
+        assert!(
+            render(ColorDepth::Ansi256, false).iter().all(
+                |(fg, bg)| matches!(
+                    fg,
+                    Color::Indexed(16..)
+                ) && *bg == Color::Indexed(16)
+            )
+        );
+        assert!(
+            render(ColorDepth::Ansi16, false).iter().all(
+                |(fg, bg)| !matches!(
+                    fg,
+                    Color::Rgb(..) | Color::Indexed(_)
+                ) && *bg == Color::Black
+            )
+        );
+        assert!(
+            render(ColorDepth::Mono, false)
+                .iter()
+                .all(|(fg, bg)| *fg == Color::Reset
+                    && *bg == Color::Reset)
+        );
+        assert_ne!(
+            render(ColorDepth::Ansi16, true),
+            render(ColorDepth::Ansi16, false)
+        );
+    }
+    #[test]
+    pub fn test_animation() {
+        use super::*;
+        use animation::{AnimationDirection, Easing};
+        let render = |animation: Option<Animation>,
+                      phase: f32| {
+            let mut buffer =
+                Buffer::empty(Rect::new(0, 0, 22, 3));
+            let mut rule = Rule::new()
+                .with_gradient(colorgrad::preset::rainbow())
+                .phase(phase);
+            rule.animation = animation;
+            rule.render(buffer.area, &mut buffer);
+            (1..21)
+                .map(|x| match buffer[(x, 1)].fg {
+                    Color::Rgb(r, g, b) => [r, g, b],
+                    c => panic!("expected rgb, got {c:?}"),
+                })
+                .collect::<Vec<_>>()
+        };
+        let still = render(None, 0.0);
+        let scroll = Animation::scroll().speed(0.5);
+        // the same phase always gives the same frame
+        assert_eq!(render(Some(scroll), 0.0), still);
+        assert_eq!(render(Some(scroll), 2.0), still);
+        assert_eq!(
+            render(Some(scroll), 0.7),
+            render(Some(scroll), 0.7)
+        );
+        // half a cycle moves the gradient by half the rule
+        let moved = render(Some(scroll), 1.0);
+        assert_ne!(moved, still);
+        let c =
+            colorgrad::preset::rainbow().at(0.5).clamp();
+        assert_eq!(
+            moved[0],
+            [c.r, c.g, c.b].map(|c| (c * 255.0) as u8)
+        );
+        let backward =
+            scroll.direction(AnimationDirection::Backward);
+        assert_eq!(render(Some(backward), 1.0), moved);
+        assert_eq!(
+            Animation::scroll()
+                .direction(AnimationDirection::Alternate)
+                .progress(1.5),
+            0.5
+        );
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        // the pulse is darkest at the start of a cycle
+        let sum = |colors: Vec<[u8; 3]>| {
+            colors
+                .iter()
+                .flatten()
+                .map(|c| *c as u32)
+                .sum::<u32>()
+        };
+        let pulse = Some(Animation::pulse());
+        assert!(
+            sum(render(pulse, 0.0))
+                < sum(render(pulse, 0.5))
+        );
+        assert_eq!(render(pulse, 0.5), still);
+        // the shimmer band brightens the middle, but not the ends
+        let shimmer =
+            render(Some(Animation::shimmer()), 0.5);
+        assert!(
+            sum(shimmer[8..12].to_vec())
+                > sum(still[8..12].to_vec())
+        );
+        assert_eq!(shimmer[..3], still[..3]);
+    }
+    #[test]
+    pub fn test_progress() {
+        use super::*;
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 16, 3));
+        let bar = || {
+            Rule::from_set(presets::progress::LINE)
+                .start("├")
+                .end("┤")
+        };
+        bar()
+            .progress(0.5)
+            .vertical_alignment(VerticalAlignment::Top)
+            .area_margin(Margin::new(1, 0))
+            .render(buffer.area, &mut buffer);
+        bar()
+            .progress(0.0)
+            .render(buffer.area, &mut buffer);
+        bar()
+            .progress(1.0)
+            .vertical_alignment(VerticalAlignment::Bottom)
+            .area_margin(Margin::new(1, 0))
+            .render(buffer.area, &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            " ├━━━━━╸──────┤ ",
+            " ├────────────┤ ",
+            " ├━━━━━━━━━━━━┤ ",
+        ]);
+        assert_eq!(buffer, expected);
+        buffer = Buffer::empty(Rect::new(0, 0, 12, 3));
+        Rule::from_set(presets::progress::BLOCK)
+            .progress(0.33)
+            .smooth_progress(true)
+            .with_gradient(colorgrad::preset::warm())
+            .render(buffer.area, &mut buffer);
+        assert_eq!(
+            (1..11)
+                .map(|x| buffer[(x, 1)].symbol())
+                .collect::<String>(),
+            "███▎      "
+        );
+        // the gradient only colors the filled part
+        assert!(matches!(
+            buffer[(4, 1)].fg,
+            Color::Rgb(..)
+        ));
+        assert_eq!(buffer[(5, 1)].fg, Color::Reset);
+        buffer = Buffer::empty(Rect::new(0, 0, 3, 8));
+        Rule::from_set(presets::progress::BLOCK)
+            .vertical()
+            .progress(0.3)
+            .smooth_progress(true)
+            .render(buffer.area, &mut buffer);
+        assert_eq!(
+            (0..8)
+                .map(|y| buffer[(1, y)].symbol())
+                .collect::<String>(),
+            "     ▆█ "
+        );
+    }
+    #[test]
+    pub fn test_thickness() {
+        use super::*;
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 8, 7));
+        Rule::default()
+            .layers(vec![
+                presets::bands::UPPER,
+                presets::bands::LOWER,
+            ])
+            .vertical_alignment(VerticalAlignment::Top)
+            .render(buffer.area, &mut buffer);
+        Rule::from_set(presets::test_sets::HORIZONTAL)
+            .thickness(3)
+            .vertical_alignment(VerticalAlignment::Bottom)
+            .render(buffer.area, &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "        ",
+            " ▀▀▀▀▀▀ ",
+            " ▄▄▄▄▄▄ ",
+            " +──+─+ ",
+            " +──+─+ ",
+            " +──+─+ ",
+            "        ",
+        ]);
+        assert_eq!(buffer, expected);
+        buffer = Buffer::empty(Rect::new(0, 0, 5, 5));
+        Rule::from_set(presets::bands::LEFT)
+            .vertical()
+            .layers(vec![
+                presets::bands::LEFT,
+                presets::bands::RIGHT,
+            ])
+            .render(buffer.area, &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "     ",
+            "  ▌▐ ",
+            "  ▌▐ ",
+            "  ▌▐ ",
+            "     ",
+        ]);
+        assert_eq!(buffer, expected);
+        // one color per row
+        buffer = Buffer::empty(Rect::new(0, 0, 8, 4));
+        Rule::default()
+            .with_gradient(colorgrad::preset::rd_bu())
+            .thickness(2)
+            .gradient_axis(GradientAxis::Across)
+            .vertical_alignment(VerticalAlignment::Top)
+            .render(buffer.area, &mut buffer);
+        let fg = |x, y| buffer[(x, y)].fg;
+        assert_eq!(fg(1, 1), fg(6, 1));
+        assert_eq!(fg(1, 2), fg(6, 2));
+        assert_ne!(fg(1, 1), fg(1, 2));
+    }
+    #[test]
+    pub fn test_layout() {
+        use super::*;
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 12, 3));
+        let rule =
+            Rule::from_set(presets::test_sets::HORIZONTAL);
+        let layout = rule.layout(buffer.area);
+        rule.render(buffer.area, &mut buffer);
+        assert_eq!(layout.origin, Position::new(1, 1));
+        assert_eq!(layout.len(), 10);
+        assert_eq!(
+            (layout.rep_1.clone(), layout.center.clone()),
+            (1..5, 5..6)
+        );
+        assert_eq!(layout.track(), 1..9);
+        let center = layout.rect(RulePart::Center);
+        assert_eq!(center, Rect::new(6, 1, 1, 1));
+        assert_eq!(
+            buffer[center.as_position()].symbol(),
+            "+"
+        );
+        assert_eq!(
+            layout.part_at(Position::new(3, 1)),
+            Some(RulePart::Rep1)
+        );
+        assert_eq!(
+            layout.part_at(Position::new(10, 1)),
+            Some(RulePart::End)
+        );
+        assert_eq!(
+            layout.part_at(Position::new(3, 0)),
+            None
+        );
+        assert_eq!(
+            layout.part_at(Position::new(11, 1)),
+            None
+        );
+        // every row of a thick rule, vertical ones go left to right
+        let layouts = Rule::new_vertical()
+            .thickness(3)
+            .padding(Padding::new(0, 0, 2, 1))
+            .layouts(Rect::new(0, 0, 7, 10));
+        assert_eq!(layouts.len(), 3);
+        assert_eq!(
+            layouts
+                .iter()
+                .map(|l| l.origin.x)
+                .collect::<Vec<_>>(),
+            [2, 3, 4]
+        );
+        assert_eq!(layouts[0].padding_before, 0..2);
+        assert_eq!(
+            layouts[0].rect(RulePart::Start),
+            Rect::new(2, 3, 1, 1)
+        );
+        #[allow(deprecated)]
+        let segment = create_segment!(
+            presets::test_sets::HORIZONTAL,
+            0,
+            0,
+            7,
+            Orientation::Horizontal,
+            Alignment::Center,
+            VerticalAlignment::Center,
+            0,
+            0
+        );
+        assert_eq!(segment, "+──+──+");
+    }
+    #[test]
+    pub fn test_gradient_text() {
+        use super::*;
+        use ratatui::style::{Modifier, Stylize};
+        let warm = colorgrad::preset::warm();
+        let colors: Vec<Color> =
+            warm.colors(4).iter().map(text::rgb).collect();
+        // every span takes part and keeps its modifiers
+        let line =
+            Line::from(vec!["ab".bold(), "cd".italic()])
+                .gradient(&warm, BgMode::None);
+        assert_eq!(line.spans.len(), 4);
+        assert_eq!(line.to_string(), "abcd");
+        assert_eq!(
+            line.spans
+                .iter()
+                .map(|s| s.style.fg.unwrap())
+                .collect::<Vec<_>>(),
+            colors
+        );
+        assert!(
+            line.spans[1]
+                .style
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+        assert!(
+            line.spans[2]
+                .style
+                .add_modifier
+                .contains(Modifier::ITALIC)
+        );
+        // wide graphemes take the color of their first cell
+        let wide = "日本".gradient(&warm, Color::Black);
+        assert_eq!(wide.spans[1].style.fg, Some(colors[2]));
+        assert_eq!(
+            wide.spans[1].style.bg,
+            Some(Color::Black)
+        );
+        let bg = colorgrad::preset::cool();
+        let both =
+            "abcd".gradient(&warm, BgMode::Gradient(&bg));
+        assert_eq!(
+            both.spans[3].style.bg,
+            Some(text::rgb(&bg.colors(4)[3]))
+        );
+        // the macro takes owned, boxed and borrowed gradients
+        let boxed: Box<dyn Gradient> =
+            Box::new(warm.clone());
+        let borrowed: &dyn Gradient = &warm;
+        let spans = generate_gradient_text!("abcd", warm);
+        assert_eq!(
+            spans,
+            generate_gradient_text!("abcd", boxed)
+        );
+        assert_eq!(
+            spans,
+            generate_gradient_text!("abcd", borrowed)
+        );
+        assert_eq!(
+            generate_gradient_text!(
+                "abcd",
+                boxed,
+                &Bg::Gradient
+            )[0]
+            .style
+            .bg,
+            Some(colors[0])
+        );
+    }
+    #[test]
+    pub fn test_text_helpers() {
+        use super::*;
+        use ratatui::text::{Span, Text};
+        let warm = colorgrad::preset::warm();
+        let fg =
+            |line: &Line, i: usize| line.spans[i].style.fg;
+        let text = Text::from("ab cd\nef");
+        // every line gets the whole gradient
+        let lines = text
+            .clone()
+            .gradient_lines(&warm, BgMode::None);
+        assert_eq!(
+            fg(&lines.lines[0], 0),
+            fg(&lines.lines[1], 0)
+        );
+        // one run through both lines
+        let across = text
+            .clone()
+            .gradient_across(&warm, BgMode::None);
+        let colors: Vec<Color> =
+            warm.colors(7).iter().map(text::rgb).collect();
+        assert_eq!(
+            fg(&across.lines[1], 0),
+            Some(colors[5])
+        );
+        assert_eq!(
+            fg(&across.lines[1], 1),
+            Some(colors[6])
+        );
+        // one color per line
+        let vertical = text
+            .clone()
+            .gradient_vertical(&warm, BgMode::None);
+        let colors: Vec<Color> =
+            warm.colors(2).iter().map(text::rgb).collect();
+        assert!(
+            vertical.lines[0]
+                .iter()
+                .all(|s| s.style.fg == Some(colors[0]))
+        );
+        assert!(
+            vertical.lines[1]
+                .iter()
+                .all(|s| s.style.fg == Some(colors[1]))
+        );
+        // one color per word, spaces keep the color of the word before them
+        let words = text
+            .clone()
+            .gradient_words(&warm, BgMode::None);
+        let colors: Vec<Color> =
+            warm.colors(3).iter().map(text::rgb).collect();
+        assert_eq!(
+            words.lines[0]
+                .iter()
+                .map(|s| s.style.fg.unwrap())
+                .collect::<Vec<_>>(),
+            [
+                colors[0], colors[0], colors[0], colors[1],
+                colors[1]
+            ]
+        );
+        assert_eq!(fg(&words.lines[1], 0), Some(colors[2]));
+        // a line on its own spreads the gradient over its words
+        let line =
+            "ab cd".gradient_words(&warm, BgMode::None);
+        assert_eq!(
+            fg(&line, 4),
+            Some(text::rgb(&warm.colors(2)[1]))
+        );
+        // rainbow per grapheme
+        let rainbow: Vec<Color> =
+            colorgrad::preset::rainbow()
+                .colors(5)
+                .iter()
+                .map(text::rgb)
+                .collect();
+        let line = Span::from("日本語ab").rainbow();
+        assert_eq!(
+            line.iter()
+                .map(|s| s.style.fg.unwrap())
+                .collect::<Vec<_>>(),
+            rainbow
+        );
+        assert_eq!(text.rainbow().lines[1].spans.len(), 2);
+    }
+    #[test]
+    pub fn test_rule_block() {
+        use super::*;
+        let area = Rect::new(0, 0, 8, 5);
+        let mut buffer = Buffer::empty(area);
+        let block = RuleBlock::new()
+            .with_gradient(colorgrad::preset::warm())
+            .title_top("Hi")
+            .title_bottom(Line::from("ok").right_aligned())
+            .title_left("L");
+        assert_eq!(
+            block.inner(area),
+            Rect::new(1, 1, 6, 3)
+        );
+        block.render(area, &mut buffer);
+        let symbols = |buffer: &Buffer| {
+            buffer
+                .content
+                .chunks(buffer.area.width as usize)
+                .map(|row| {
+                    row.iter()
+                        .map(|c| c.symbol())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            symbols(&buffer),
+            [
+                "┌Hi────┐",
+                "L      │",
+                "│      │",
+                "│      │",
+                "└────ok┘",
+            ]
+        );
+        // one gradient, clockwise from the top left corner
+        let colors: Vec<Color> = colorgrad::preset::warm()
+            .colors(22)
+            .iter()
+            .map(text::rgb)
+            .collect();
+        assert_eq!(buffer[(0, 0)].fg, colors[0]);
+        assert_eq!(buffer[(7, 1)].fg, colors[8]);
+        assert_eq!(buffer[(7, 4)].fg, colors[11]);
+        assert_eq!(buffer[(0, 2)].fg, colors[20]);
+        // wide graphemes hide the next cell, but only fit on the top and bottom
+        buffer = Buffer::empty(area);
+        RuleBlock::new()
+            .title_top("界")
+            .title_bottom(Line::from("界").right_aligned())
+            .title_left("x界y")
+            .title(Side::Right, Line::from("ab").centered())
+            .render(area, &mut buffer);
+        assert_eq!(
+            symbols(&buffer),
+            [
+                "┌界 ────┐",
+                "x      a",
+                "│      b",
+                "│      │",
+                "└────界 ┘",
+            ]
+        );
+        // a same bg without a gradient takes the color of the border style, if it has one
+        for (border, bg) in [
+            (ratatui::style::Style::new(), Color::Blue),
+            (Color::Red.into(), Color::Red),
+        ] {
+            buffer = Buffer::empty(Rect::new(0, 0, 3, 3));
+            buffer.set_style(
+                buffer.area,
+                ratatui::style::Style::new()
+                    .bg(Color::Blue),
+            );
+            RuleBlock::new()
+                .border_style(border)
+                .bg(Bg::Gradient)
+                .render(buffer.area, &mut buffer);
+            assert_eq!(buffer[(0, 0)].bg, bg);
+        }
+        // a wide symbol that would run past the end of a side is left out
+        assert_eq!(
+            block::cells(
+                "─界",
+                &Orientation::Horizontal,
+                2
+            ),
+            ["─", " "]
+        );
+        assert_eq!(
+            block::cells(
+                "─界",
+                &Orientation::Horizontal,
+                3
+            ),
+            ["─", "界", ""]
+        );
+        // per-side overrides and border families from the registry
+        buffer = Buffer::empty(Rect::new(0, 0, 5, 3));
+        RuleBlock::from_preset("borders::double")
+            .unwrap()
+            .side(
+                Side::Bottom,
+                presets::borders::plain::BOTTOM,
+            )
+            .render(buffer.area, &mut buffer);
+        assert_eq!(
+            symbols(&buffer),
+            ["╔═══╗", "║   ║", "└───┘"]
+        );
+        assert!(
+            RuleBlock::from_preset("borders::nope")
+                .is_none()
+        );
+    }
+    #[test]
+    pub fn test_gradient_field() {
+        use super::*;
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 9, 9));
+        let field = GradientField::radial();
+        let rule = || {
+            Rule::default()
+                .with_gradient(colorgrad::preset::sinebow())
+                .gradient_field(field)
+        };
+        rule().render(buffer.area, &mut buffer);
+        let crossing = buffer[(4, 4)].fg;
+        rule().vertical().render(buffer.area, &mut buffer);
+        // one color field for both rules
+        assert_eq!(buffer[(4, 4)].fg, crossing);
+        assert_eq!(buffer[(2, 4)].fg, buffer[(4, 2)].fg);
+        assert_eq!(buffer[(1, 4)].fg, buffer[(7, 4)].fg);
+        // parallel rules line up
+        buffer = Buffer::empty(Rect::new(0, 0, 12, 5));
+        for alignment in [
+            VerticalAlignment::Top,
+            VerticalAlignment::Bottom,
+        ] {
+            Rule::default()
+                .with_gradient(colorgrad::preset::rainbow())
+                .gradient_field(GradientField::linear(0.0))
+                .vertical_alignment(alignment)
+                .render(buffer.area, &mut buffer);
+        }
+        for x in 1..11 {
+            assert_eq!(
+                buffer[(x, 1)].fg,
+                buffer[(x, 3)].fg
+            );
+        }
+        assert_ne!(buffer[(1, 1)].fg, buffer[(10, 1)].fg);
+        let area = Rect::new(10, 10, 10, 10);
+        let t = |field: GradientField, x, y| {
+            field.t(Position::new(x, y), area)
+        };
+        assert!(
+            t(GradientField::linear(90.0), 15, 10) < 0.1
+        );
+        assert!(
+            t(GradientField::linear(90.0), 15, 19) > 0.9
+        );
+        assert!(t(GradientField::radial(), 15, 15) < 0.15);
+        // a quarter turn clockwise from 12 o'clock
+        assert!(
+            (t(GradientField::conic(), 19, 15) - 0.25)
+                .abs()
+                < 0.05
+        );
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn test_serde_round_trip() {
+        use super::*;
+        use gradient::GradientInterpolation;
+        use ratatui::style::Stylize;
+        let rule =
+            Rule::from_set(presets::borders::double::TOP)
+                .with_gradient_spec(
+                    GradientSpec::stops_at([
+                        ("#ff0000", 0.0),
+                        ("gold", 0.3),
+                        ("rgb(0, 0, 255)", 1.0),
+                    ])
+                    .interpolation(
+                        GradientInterpolation::CatmullRom,
+                    ),
+                )
+                .unwrap()
+                .bg_gradient_spec(
+                    GradientSpec::preset("viridis")
+                        .sharp(5, 0.1),
+                )
+                .unwrap()
+                .padding(Padding::new(1, 2, 0, 1))
+                .horizontal_alignment(Alignment::Right)
+                .area_margin(Margin::new(0, 1))
+                .label(
+                    Line::from(vec![
+                        "err".red(),
+                        "ors".bold(),
+                    ])
+                    .centered(),
+                )
+                .label_position(LabelPosition::Left)
+                .marker(
+                    Marker::ratio(0.3, "┼")
+                        .style(Color::Red),
+                )
+                .ticks(Ticks::new(3, "┴").major(9, "┼"));
+        let json = serde_json::to_string(&rule).unwrap();
+        let back: Rule =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::to_string(&back).unwrap()
+        );
+        assert_eq!(back.label, rule.label);
+        assert_eq!(
+            back.label.as_ref().unwrap().alignment,
+            Some(Alignment::Center)
+        );
+        let mut expected =
+            Buffer::empty(Rect::new(0, 0, 30, 5));
+        let mut buffer = expected.clone();
+        rule.render(expected.area, &mut expected);
+        back.render(buffer.area, &mut buffer);
+        assert_eq!(buffer, expected);
+        // missing fields use the defaults of `Rule::new`
+        let partial: Rule = serde_json::from_str(
+            r#"{"gradient": {"preset": "warm"}, "label": "Logs", "bg": {"Solid": "blue"}}"#,
+        )
+        .unwrap();
+        assert_eq!(partial.area_margin, Margin::new(1, 1));
+        assert_eq!(partial.label, Some(Line::from("Logs")));
+        assert!(matches!(
+            partial.bg,
+            Bg::Solid(Color::Blue)
+        ));
+        assert_eq!(
+            partial.gradient.unwrap().at(0.5),
+            colorgrad::preset::warm().at(0.5)
+        );
+        let Err(err) = serde_json::from_str::<Rule>(
+            r#"{"gradient": {"preset": "not_a_preset"}}"#,
+        ) else {
+            panic!("unknown presets should be rejected");
+        };
+        assert!(err.to_string().contains("not_a_preset"));
+        // layers can be preset names too
+        let bands: Rule = serde_json::from_str(
+            r#"{"layers": ["bands::UPPER", "bands::LOWER"], "thickness": 2}"#,
+        )
+        .unwrap();
+        assert_eq!(bands.layers[1].rep_1, "▄");
+        // gradients without a spec can't be written back
+        assert!(
+            serde_json::to_string(
+                &Rule::new().with_gradient(
+                    colorgrad::preset::warm()
+                )
+            )
+            .is_err()
+        );
+        let mut mixed =
+            GradientSpec::stops_at([("red", 0.0)]);
+        mixed.stops.push(gradient::ColorStop::new("blue"));
+        assert_eq!(
+            mixed.build().err(),
+            Some(GradientSpecError::MixedPositions)
+        );
+        assert_eq!(GradientSpec::PRESETS.len(), 38);
+        for name in GradientSpec::PRESETS {
+            assert!(
+                GradientSpec::preset(*name).build().is_ok()
+            );
+        }
+    }
+    #[cfg(feature = "theme")]
+    #[test]
+    pub fn test_theme() {
+        use super::*;
+        use theme::{Theme, ThemeFormat};
+        let toml = r##"
+[section]
+label = "Logs"
+gradient = { preset = "warm" }
+symbol_set = { start = "├", end = "┤" }
+
+[sidebar-divider]
+orientation = "Vertical"
+bg = { Solid = "#1e1e2e" }
+"##;
+        let json = r##"{
+    "section": {
+        "label": "Logs",
+        "gradient": { "preset": "warm" },
+        "symbol_set": { "start": "├", "end": "┤" }
+    },
+    "sidebar-divider": {
+        "orientation": "Vertical",
+        "bg": { "Solid": "#1e1e2e" }
+    }
+}"##;
+        let ron = r##"{
+    "section": (
+        label: Some("Logs"),
+        gradient: Some((preset: Some("warm"))),
+        symbol_set: (start: "├", end: "┤"),
+    ),
+    "sidebar-divider": (
+        orientation: Vertical,
+        bg: Solid("#1e1e2e"),
+    ),
+}"##;
+        for (src, format) in [
+            (toml, ThemeFormat::Toml),
+            (json, ThemeFormat::Json),
+            (ron, ThemeFormat::Ron),
+        ] {
+            let theme = Theme::parse(src, format).unwrap();
+            assert_eq!(
+                theme.names().collect::<Vec<_>>(),
+                ["section", "sidebar-divider"]
+            );
+            let mut buffer =
+                Buffer::empty(Rect::new(0, 0, 14, 3));
+            theme["section"]
+                .render_ref(buffer.area, &mut buffer);
+            assert_eq!(buffer[(1, 1)].symbol(), "├");
+            assert_eq!(buffer[(5, 1)].symbol(), "L");
+            assert!(matches!(
+                buffer[(1, 1)].fg,
+                Color::Rgb(..)
+            ));
+            let divider = &theme["sidebar-divider"];
+            assert_eq!(
+                divider.orientation,
+                Orientation::Vertical
+            );
+            assert!(matches!(
+                divider.bg,
+                Bg::Solid(Color::Rgb(0x1e, 0x1e, 0x2e))
+            ));
+        }
+    }
+    #[cfg(feature = "theme")]
+    #[test]
+    pub fn test_theme_errors() {
+        use super::*;
+        use theme::{Theme, ThemeError, ThemeFormat};
+        let bad_preset = [
+            (
+                "[a]\nlabel = \"x\"\n\n[b]\ngradient = { preset = \"wram\" }\n",
+                ThemeFormat::Toml,
+                (5, 23),
+            ),
+            (
+                "{\n  \"a\": { \"label\": \"x\" },\n  \"b\": {\n    \"gradient\": { \"preset\": \"wram\" }\n  }\n}",
+                ThemeFormat::Json,
+                (4, 36),
+            ),
+            (
+                "{\n  \"a\": (label: Some(\"x\")),\n  \"b\": (\n    gradient: Some((preset: Some(\"wram\"))),\n  ),\n}",
+                ThemeFormat::Ron,
+                (4, 41),
+            ),
+        ];
+        let bad_color = [
+            (
+                "[a]\nbg = { Solid = \"#ggg\" }\n",
+                ThemeFormat::Toml,
+                (2, 6),
+            ),
+            (
+                "{\n  \"a\": { \"bg\": { \"Solid\": \"#ggg\" } }\n}",
+                ThemeFormat::Json,
+                (2, 34),
+            ),
+            (
+                "{\n  \"a\": (bg: Solid(\"#ggg\")),\n}",
+                ThemeFormat::Ron,
+                (2, 25),
+            ),
+            // a gradient stop
+            (
+                "{\n  \"a\": {\n    \"gradient\": { \"stops\": [{ \"color\": \"#ggg\" }] }, \"label\": \"x\"\n  }\n}",
+                ThemeFormat::Json,
+                (3, 47),
+            ),
+        ];
+        for (src, format, (line, column)) in
+            bad_preset.into_iter().chain(bad_color)
+        {
+            let Err(ThemeError::Parse {
+                line: l,
+                column: c,
+                message,
+                ..
+            }) = Theme::parse(src, format)
+            else {
+                panic!("expected a parse error");
+            };
+            assert_eq!(
+                (l, c),
+                (Some(line), Some(column)),
+                "{format:?}: {message}"
+            );
+            assert!(
+                ["wram", "Colors", "#ggg"]
+                    .iter()
+                    .any(|m| message.contains(m))
+            );
+        }
+    }
+    #[cfg(feature = "watch")]
+    #[test]
+    pub fn test_theme_reload() {
+        use super::*;
+        use std::{
+            fs,
+            time::{Duration, Instant},
+        };
+        use theme::Theme;
+        let path = std::env::temp_dir().join(format!(
+            "tui-rule-reload-{}.toml",
+            std::process::id()
+        ));
+        fs::write(&path, "[a]\nlabel = \"old\"\n").unwrap();
+        let watcher = Theme::watch(&path, || {}).unwrap();
+        assert!(watcher.reload().is_none());
+        // written next to it and moved into place like an editor would,
+        // so the watcher never sees a half-written file
+        let staged = path.with_extension("toml.tmp");
+        fs::write(&staged, "[a]\nlabel = \"new\"\n[b]\n")
+            .unwrap();
+        fs::rename(&staged, &path).unwrap();
+        let start = Instant::now();
+        let theme = loop {
+            if let Some(theme) = watcher.reload() {
+                break theme.unwrap();
+            }
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "no change seen"
+            );
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            theme.names().collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+        assert_eq!(
+            theme["a"].to_plain_string(9),
+            "── new ──"
+        );
+    }
+    #[test]
+    pub fn test_markers() {
+        use super::*;
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 22, 3));
+        Rule::default()
+            .start("├")
+            .end("┤")
+            .area_margin(Margin::new(0, 0))
+            .vertical_alignment(VerticalAlignment::Top)
+            .markers(vec![
+                Marker::ratio(0.3, "┼"),
+                Marker::at(0, "<"),
+                Marker::from_end(0, ">"),
+                // off the track
+                Marker::at(20, "x"),
+            ])
+            .render(buffer.area, &mut buffer);
+        Rule::default()
+            .area_margin(Margin::new(0, 0))
+            .ticks(Ticks::new(2, "┴").major(10, "┼"))
+            .render(buffer.area, &mut buffer);
+        // over the ticks, under the label
+        Rule::default()
+            .area_margin(Margin::new(0, 0))
+            .vertical_alignment(VerticalAlignment::Bottom)
+            .ticks(Ticks::new(1, "·"))
+            .marker(Marker::ratio(0.5, "│"))
+            .label("ab")
+            .label_gap(0, 0)
+            .label_position(LabelPosition::Left)
+            .render(buffer.area, &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "├<─────┼────────────>┤",
+            "─┼─┴─┴─┴─┴─┼─┴─┴─┴─┴──",
+            "─·ab·······│·········─",
+        ]);
+        assert_eq!(buffer, expected);
+        // markers keep the gradient unless their style has a color
+        buffer = Buffer::empty(Rect::new(0, 0, 1, 12));
+        Rule::new_vertical()
+            .with_gradient(colorgrad::preset::warm())
+            .area_margin(Margin::new(0, 0))
+            .marker(Marker::at(2, "a"))
+            .marker(
+                Marker::from_end(2, "b").style(Color::Red),
+            )
+            .render(buffer.area, &mut buffer);
+        assert_eq!(buffer[(0, 3)].symbol(), "a");
+        assert_eq!(buffer[(0, 8)].symbol(), "b");
+        assert!(matches!(
+            buffer[(0, 3)].fg,
+            Color::Rgb(..)
+        ));
+        assert_eq!(buffer[(0, 8)].fg, Color::Red);
+        // a symbol of several graphemes takes a cell for each
+        buffer = Buffer::empty(Rect::new(0, 0, 1, 12));
+        Rule::from_set(presets::test_sets::VERTICAL)
+            .vertical()
+            .area_margin(Margin::new(0, 0))
+            .marker(Marker::at(1, "xy"))
+            .marker(Marker::at(5, "z"))
+            .marker(Marker::from_end(0, "e"))
+            .render(buffer.area, &mut buffer);
+        let column: String = buffer
+            .content
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert_eq!(column, "+│xy││z│││e+");
+        // and so do ticks, a tick starting under a longer one is left out
+        for (every, expected) in
+            [(4, "+ab││ab││ab+"), (1, "+ababababab+")]
+        {
+            buffer = Buffer::empty(Rect::new(0, 0, 1, 12));
+            Rule::from_set(presets::test_sets::VERTICAL)
+                .vertical()
+                .area_margin(Margin::new(0, 0))
+                .ticks(Ticks::new(every, "ab"))
+                .render(buffer.area, &mut buffer);
+            let column: String = buffer
+                .content
+                .iter()
+                .map(|c| c.symbol())
+                .collect();
+            assert_eq!(column, expected);
+        }
+        assert_eq!(
+            MarkerPosition::Ratio(1.0).cell(10),
+            Some(9)
+        );
+        assert_eq!(
+            MarkerPosition::FromEnd(10).cell(10),
+            None
+        );
+    }
+    #[test]
+    pub fn test_scale() {
+        use super::*;
+        use ratatui::style::Modifier;
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 21, 5));
+        Scale::new(0.0, 20.0, 5.0)
+            .format(|v| format!("{v}s"))
+            .render(Rect::new(0, 0, 21, 2), &mut buffer);
+        // the labels that would touch the one before them are left out
+        Scale::new(0.0, 1.0, 0.1)
+            .labels(Side::Top)
+            .render(Rect::new(0, 2, 21, 3), &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "┬────┬────┬────┬────┬",
+            "0s   5s  10s  15s 20s",
+            "",
+            "0.0  0.3   0.6   0.9 ",
+            "┴─┴─┴─┴─┴─┴─┴─┴─┴─┴─┴",
+        ]);
+        assert_eq!(buffer, expected);
+        // labels take the color of their tick
+        buffer = Buffer::empty(Rect::new(0, 0, 4, 7));
+        Scale::new(10.0, 0.0, -5.0)
+            .vertical()
+            .with_gradient(colorgrad::preset::warm())
+            .label_style(Modifier::BOLD)
+            .render(buffer.area, &mut buffer);
+        assert_eq!(
+            Scale::new(10.0, 0.0, -5.0)
+                .vertical()
+                .ticks(buffer.area)
+                .into_iter()
+                .map(|(pos, v)| (pos.y, v))
+                .collect::<Vec<_>>(),
+            [(0, 10.0), (3, 5.0), (6, 0.0)]
+        );
+        assert_eq!(buffer[(0, 3)].symbol(), "├");
+        assert_eq!(buffer[(1, 3)].symbol(), "5");
+        assert_eq!(buffer[(1, 3)].fg, buffer[(0, 3)].fg);
+        assert!(
+            buffer[(1, 3)]
+                .modifier
+                .contains(Modifier::BOLD)
+        );
+    }
+    #[cfg(feature = "crossterm")]
+    #[test]
+    pub fn test_splitter() {
+        use super::*;
+        use crossterm::event::{
+            KeyCode, KeyEvent, KeyModifiers, MouseButton,
+            MouseEvent, MouseEventKind,
+        };
+        use ratatui::{
+            layout::Constraint, style::Modifier,
+            widgets::StatefulWidget,
+        };
+        let mouse = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        let key = |code| KeyEvent::from(code);
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 10, 3));
+        let mut state = SplitterState::new(4).min(2).max(7);
+        Splitter::vertical().render(
+            buffer.area,
+            &mut buffer,
+            &mut state,
+        );
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "    │     ",
+            "    │     ",
+            "    │     ",
+        ]);
+        assert_eq!(buffer, expected);
+        assert_eq!(
+            state.handle_mouse(mouse(
+                MouseEventKind::Moved,
+                4,
+                1
+            )),
+            None
+        );
+        assert!(state.hovered);
+        Splitter::vertical().render(
+            buffer.area,
+            &mut buffer,
+            &mut state,
+        );
+        assert!(
+            buffer[(4, 1)]
+                .modifier
+                .contains(Modifier::BOLD)
+        );
+        // dragged past `max` and `min`
+        let left = MouseButton::Left;
+        state.handle_mouse(mouse(
+            MouseEventKind::Down(left),
+            4,
+            0,
+        ));
+        assert!(state.dragging && state.focused);
+        assert_eq!(
+            state.handle_mouse(mouse(
+                MouseEventKind::Drag(left),
+                9,
+                2
+            )),
+            Some(7)
+        );
+        assert_eq!(
+            state.handle_mouse(mouse(
+                MouseEventKind::Drag(left),
+                0,
+                2
+            )),
+            Some(2)
+        );
+        state.handle_mouse(mouse(
+            MouseEventKind::Up(left),
+            0,
+            2,
+        ));
+        assert!(!state.dragging);
+        assert_eq!(
+            state.constraints(),
+            [
+                Constraint::Length(2),
+                Constraint::Length(1),
+                Constraint::Fill(1)
+            ]
+        );
+        // nudged while focused
+        assert_eq!(
+            state.handle_key(key(KeyCode::Right)),
+            Some(3)
+        );
+        assert_eq!(
+            state.handle_key(key(KeyCode::Up)),
+            None
+        );
+        assert_eq!(
+            state.handle_key(key(KeyCode::End)),
+            Some(7)
+        );
+        state.handle_key(key(KeyCode::Esc));
+        assert_eq!(
+            state.handle_key(key(KeyCode::Left)),
+            None
+        );
+        // kept inside an area that shrank
+        Splitter::vertical().render(
+            Rect::new(0, 0, 5, 3),
+            &mut buffer,
+            &mut state,
+        );
+        assert_eq!(state.position, 4);
+        assert_eq!(
+            state.splitter_area(),
+            Rect::new(4, 0, 1, 3)
+        );
+    }
+    #[test]
+    pub fn test_rule_state() {
+        use super::*;
+        use ratatui::widgets::StatefulWidget;
+        use std::time::Duration;
+        // `Widget::render` takes the same name
+        let area = Rect::new(0, 0, 12, 3);
+        let rule = Rule::from_set(presets::progress::LINE)
+            .with_gradient(colorgrad::preset::sinebow())
+            .animation(Animation::scroll());
+        let mut state = RuleState::new().progress(0.5);
+        state.tick(Duration::from_millis(1500));
+        let mut buffer = Buffer::empty(area);
+        StatefulWidget::render(
+            &rule,
+            area,
+            &mut buffer,
+            &mut state,
+        );
+        // the same as a rule with the phase and progress of the state
+        let mut expected = Buffer::empty(area);
+        Rule::from_set(presets::progress::LINE)
+            .with_gradient(colorgrad::preset::sinebow())
+            .animation(Animation::scroll())
+            .phase(1.5)
+            .progress(0.5)
+            .render_ref(area, &mut expected);
+        assert_eq!(buffer, expected);
+        assert_eq!(state.area(), area);
+        assert_eq!(state.layouts(), [rule.layout(area)]);
+        assert_eq!(
+            state.part_at(Position::new(1, 1)),
+            Some(RulePart::Rep1)
+        );
+        assert!(state.hover(Position::new(5, 1)));
+        assert!(state.hovered);
+        assert!(!state.hover(Position::new(6, 1)));
+        assert!(state.hover(Position::new(5, 0)));
+        // the layouts follow the rule
+        StatefulWidget::render(
+            rule.thickness(3),
+            area,
+            &mut buffer,
+            &mut state,
+        );
+        assert_eq!(state.layouts().len(), 3);
+        let rule = Rule::from_set(presets::progress::LINE)
+            .start("<=");
+        StatefulWidget::render(
+            &rule,
+            area,
+            &mut buffer,
+            &mut state,
+        );
+        assert_eq!(state.layouts(), [rule.layout(area)]);
+    }
+    #[test]
+    pub fn test_palette_cache() {
+        use super::*;
+        use gradient::GradientInterpolation;
+        use std::rc::Rc;
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 16, 3));
+        let narrow = Rect::new(0, 0, 12, 3);
+        let last = |rule: &Rule| rule.cache.last().unwrap();
+        let mut rule = Rule::default()
+            .with_gradient(colorgrad::preset::sinebow());
+        rule.render_ref(narrow, &mut buffer);
+        let first = last(&rule);
+        // kept while nothing changes
+        rule.render_ref(narrow, &mut buffer);
+        assert!(Rc::ptr_eq(&first, &last(&rule)));
+        // sampled again for another width, bg or gradient
+        rule = rule.bg_same_gradient();
+        rule.render_ref(narrow, &mut buffer);
+        assert!(!Rc::ptr_eq(&first, &last(&rule)));
+        let same = last(&rule);
+        rule.render_ref(buffer.area, &mut buffer);
+        assert!(!Rc::ptr_eq(&same, &last(&rule)));
+        let wide = last(&rule);
+        rule = rule.bg_gradient(colorgrad::preset::warm());
+        rule.render_ref(buffer.area, &mut buffer);
+        assert!(!Rc::ptr_eq(&wide, &last(&rule)));
+        rule =
+            rule.with_gradient(colorgrad::preset::cool());
+        rule.render_ref(buffer.area, &mut buffer);
+        let mut expected = Buffer::empty(buffer.area);
+        Rule::default()
+            .with_gradient(colorgrad::preset::cool())
+            .bg_gradient(colorgrad::preset::warm())
+            .render(buffer.area, &mut expected);
+        assert_eq!(buffer, expected);
+        // and for fields assigned without a builder
+        rule.gradient =
+            Some(Box::new(colorgrad::preset::viridis()));
+        rule.bg = Bg::Solid(Color::Black);
+        rule.render_ref(buffer.area, &mut buffer);
+        let mut expected = Buffer::empty(buffer.area);
+        Rule::default()
+            .with_gradient(colorgrad::preset::viridis())
+            .bg_solid(Color::Black)
+            .render(buffer.area, &mut expected);
+        assert_eq!(buffer, expected);
+        // the same stops with another interpolation, likely in the box that was just freed
+        let build = |interpolation| {
+            GradientSpec::stops(["#f00", "#0f0", "#00f"])
+                .interpolation(interpolation)
+                .build()
+                .unwrap()
+        };
+        rule.bg = Bg::None;
+        rule.gradient =
+            Some(build(GradientInterpolation::Linear));
+        buffer = Buffer::empty(buffer.area);
+        rule.render_ref(buffer.area, &mut buffer);
+        rule.gradient = None;
+        rule.gradient =
+            Some(build(GradientInterpolation::CatmullRom));
+        buffer = Buffer::empty(buffer.area);
+        rule.render_ref(buffer.area, &mut buffer);
+        let mut expected = Buffer::empty(buffer.area);
+        Rule {
+            gradient: Some(build(
+                GradientInterpolation::CatmullRom,
+            )),
+            ..Rule::default()
+        }
+        .render(buffer.area, &mut expected);
+        assert_eq!(buffer, expected);
+        // animated gradients are sampled on every render
+        let cached = last(&rule);
+        rule =
+            rule.animation(Animation::scroll()).phase(0.5);
+        rule.render_ref(buffer.area, &mut buffer);
+        assert!(Rc::ptr_eq(&cached, &last(&rule)));
+    }
+    #[test]
+    pub fn test_ansi() {
+        use super::*;
+        let rule = Rule::default()
+            .with_gradient(colorgrad::preset::sinebow())
+            .label("日本");
+        assert_eq!(
+            rule.to_plain_string(12),
+            "─── 日本 ───"
+        );
+        assert_eq!(
+            Rule::from_set(presets::test_sets::VERTICAL)
+                .vertical()
+                .to_plain_string(5),
+            "+\n│\n+\n│\n+"
+        );
+        let rule = Rule::default()
+            .with_gradient(colorgrad::preset::sinebow())
+            .bg_solid(Color::Black)
+            .thickness(2);
+        let ansi =
+            rule.to_ansi_string(6, ColorDepth::TrueColor);
+        // the colors of the cells in the buffer
+        let buf = rule.to_buffer(6);
+        let Color::Rgb(r, g, b) = buf[(0, 0)].fg else {
+            panic!("no gradient");
+        };
+        assert!(ansi.starts_with(&format!(
+            "\x1b[38;2;{r};{g};{b};40m─\x1b[0m"
+        )));
+        assert_eq!(ansi.lines().count(), 2);
+        assert!(ansi.ends_with("─\x1b[0m"));
+        let ansi =
+            rule.to_ansi_string(6, ColorDepth::Ansi256);
+        assert!(ansi.starts_with("\x1b[38;5;"));
+        assert_eq!(
+            rule.to_ansi_string(6, ColorDepth::Mono),
+            rule.to_plain_string(6)
+        );
+    }
+    #[test]
+    pub fn test_export() {
+        use super::*;
+        use ratatui::style::Stylize;
+        let rule = Rule::default()
+            .with_gradient(colorgrad::preset::sinebow())
+            .bg_solid(Color::Black)
+            .label(Line::from("<日>").bold());
+        let buf = rule.to_buffer(11);
+        let Color::Rgb(r, g, b) = buf[(0, 0)].fg else {
+            panic!("no gradient");
+        };
+        let first = format!("#{r:02x}{g:02x}{b:02x}");
+        let html = export::to_html(&buf, buf.area);
+        assert!(html.starts_with("<pre "));
+        assert!(html.contains(&format!(
+            "<span style=\"color:{first};background-color:#000000;\">─</span>"
+        )));
+        assert!(
+            html.contains(
+                "font-weight:bold;\">&lt;</span>"
+            )
+        );
+        assert!(html.ends_with("</pre>"));
+        let svg = export::to_svg(&buf, buf.area);
+        // 11 cells of 9 by 18 pixels
+        assert!(svg.contains("width=\"99\" height=\"18\""));
+        assert!(svg.contains(&format!(
+            "<text x=\"0\" y=\"9\" fill=\"{first}\""
+        )));
+        assert!(svg.contains(
+            "<rect x=\"0\" y=\"0\" width=\"9\" height=\"18\" fill=\"#000000\"/>"
+        ));
+        // the wide glyph takes up two cells
+        let wide = svg
+            .lines()
+            .find(|l| l.contains(">日<"))
+            .unwrap();
+        assert!(wide.contains("textLength=\"18\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+    #[test]
+    pub fn test_offset_area() {
+        use super::*;
+        // centered in an area that doesn't start at the top of the buffer
+        let mut buffer =
+            Buffer::empty(Rect::new(0, 0, 8, 10));
+        let area = Rect::new(2, 5, 6, 5);
+        Rule::from_set(presets::test_sets::HORIZONTAL)
+            .area_margin(Margin::new(0, 0))
+            .render(area, &mut buffer);
+        Rule::from_set(presets::test_sets::VERTICAL)
+            .area_margin(Margin::new(0, 0))
+            .vertical()
+            .horizontal_alignment(Alignment::Right)
+            .render(Rect::new(0, 0, 4, 4), &mut buffer);
+        #[rustfmt::skip]
+        let expected = Buffer::with_lines([
+            "   +    ",
+            "   │    ",
+            "   +    ",
+            "   +    ",
+            "        ",
+            "        ",
+            "        ",
+            "  +──+─+",
+            "        ",
+            "        ",
+        ]);
+        assert_eq!(buffer, expected);
+    }
+    proptest::proptest! {
+        #[test]
+        fn test_clipping(
+            area in (0u16..30, 0u16..20, 0u16..30, 0u16..20),
+            padding in
+                (0u16..8, 0u16..8, 0u16..8, 0u16..8),
+            margin in (0u16..4, 0u16..4),
+            alignment in (0usize..3, 0usize..3),
+            vertical: bool,
+            thickness in 1u16..5,
+            extra_rep in (0usize..6, 0usize..6),
+            label: bool,
+            progress in proptest::option::of(0f32..=1.0),
+        ) {
+            use super::*;
+            let bounds = Rect::new(0, 0, 40, 30);
+            let area =
+                Rect::new(area.0, area.1, area.2, area.3);
+            let mut buffer = Buffer::filled(
+                bounds,
+                ratatui::buffer::Cell::new("x"),
+            );
+            let mut rule =
+                Rule::from_set(presets::test_sets::HORIZONTAL)
+                .with_gradient(colorgrad::preset::warm())
+                .padding(Padding::new(
+                    padding.0, padding.1, padding.2, padding.3,
+                ))
+                .area_margin(Margin::new(margin.0, margin.1))
+                .horizontal_alignment(
+                    [
+                        Alignment::Left,
+                        Alignment::Center,
+                        Alignment::Right,
+                    ][alignment.0],
+                )
+                .vertical_alignment(
+                    [
+                        VerticalAlignment::Top,
+                        VerticalAlignment::Center,
+                        VerticalAlignment::Bottom,
+                    ][alignment.1]
+                        .clone(),
+                )
+                .thickness(thickness)
+                .extra_rep(extra_rep.0, extra_rep.1);
+            if vertical {
+                rule = rule
+                    .with_set(presets::test_sets::VERTICAL)
+                    .vertical();
+            }
+            if label {
+                rule = rule.label("Logs");
+            }
+            if let Some(ratio) = progress {
+                rule = rule.progress(ratio);
+            }
+            rule.render(area, &mut buffer);
+            let inside = area
+                .inner(Margin::new(margin.0, margin.1))
+                .intersection(bounds);
+            for pos in bounds.positions() {
+                if !inside.contains(pos) {
+                    proptest::prop_assert_eq!(
+                        buffer[pos].symbol(),
+                        "x",
+                        "{:?} written outside of {:?}",
+                        pos,
+                        area
+                    );
+                }
+            }
+        }
+    }
+}
+pub mod macros {
+    #[cfg(feature = "utils")]
+    #[macro_export]
+    macro_rules! gen_main {
+        () => {
+            fn main() -> io::Result<()> {
+                let mut terminal = ratatui::init();
+                let app_result = run(&mut terminal);
+                ratatui::restore();
+                app_result
+            }
+        };
+    }
+    #[cfg(feature = "utils")]
+    #[macro_export]
+    macro_rules! gen_example_code {
+        ($fun:item) => {
+            tui_rule::gen_use!();
+            tui_rule::gen_run!($fun);
+            tui_rule::gen_main!();
+        };
+    }
+    #[cfg(feature = "utils")]
+    #[macro_export]
+    macro_rules! gen_run {
+        ($fun:item) => {
+            $fun
+        };
+    }
+    #[cfg(feature = "utils")]
+    #[macro_export]
+    macro_rules! gen_use {
+        () => {
+            use colorgrad::Gradient;
+            use crossterm::event::{
+                self, Event, KeyCode, KeyEvent,
+                KeyEventKind,
+            };
+            use ratatui::{
+                buffer::Buffer,
+                layout::Rect,
+                prelude::{Alignment, Color, Style},
+                text::Line,
+                widgets::{Block, Widget},
+                DefaultTerminal, Frame,
+            };
+            use std::{io, rc::Rc};
+            use tui_rule::*;
+        };
+    }
+    #[macro_export]
+    macro_rules! create_raw_spans {
+        ($string:expr) => {
+            $crate::unicode_segmentation::UnicodeSegmentation::graphemes(
+                &*$string, true,
+            )
+                .map(String::from)
+                .map(ratatui::text::Span::from)
+                .collect::<Vec<ratatui::text::Span>>()
         };
     }
 }
 
-impl WidgetRef for Rule {
-    fn render_ref(
-        &self,
-        mut area_old: Rect,
-        buf: &mut Buffer,
-    ) {
+impl Rule {
+    /// the layout of every row of the rule rendered in `area`,
+    /// from the top (left for vertical rules) down.
+    /// the rows are placed inside `area` shrunk by `area_margin`,
+    /// rows that don't fit and cells past its end are clipped when rendering
+    pub fn layouts(&self, area: Rect) -> Vec<RuleLayout> {
+        let area = area.inner(self.area_margin);
         let (p_l, p_r, p_t, p_b) = (
             self.padding.left,
             self.padding.right,
             self.padding.top,
             self.padding.bottom,
         );
-        if self.orientation == Orientation::Horizontal {
-            area_old.y = match self.vertical_alignment {
-                VerticalAlignment::Top => area_old
-                    .y
-                    .saturating_sub(p_b)
-                    .saturating_add(p_t),
-                VerticalAlignment::Center => {
-                    (area_old.bottom() / 2)
-                        .saturating_sub(1 + p_b)
-                        .saturating_add(p_t)
+        let rows = self.thickness.max(1);
+        // where the band lands across the rule, from the start of the area.
+        // the padding on that axis pushes it away from its side
+        let (room, offset) = match self.orientation {
+            Orientation::Horizontal => (
+                area.height,
+                match self.vertical_alignment {
+                    VerticalAlignment::Top => 0,
+                    VerticalAlignment::Center => area
+                        .height
+                        .saturating_sub(rows)
+                        .div_ceil(2),
+                    VerticalAlignment::Bottom => {
+                        area.height.saturating_sub(rows)
+                    }
+                } as i32
+                    + p_t as i32
+                    - p_b as i32,
+            ),
+            Orientation::Vertical => (
+                area.width,
+                match self.horizontal_alignment {
+                    Alignment::Left => 0,
+                    Alignment::Center => area
+                        .width
+                        .saturating_sub(rows)
+                        .div_ceil(2),
+                    Alignment::Right => {
+                        area.width.saturating_sub(rows)
+                    }
+                } as i32
+                    + p_l as i32
+                    - p_r as i32,
+            ),
+        };
+        // the padding never pushes the band out of the area
+        let offset = offset
+            .clamp(0, room.saturating_sub(rows) as i32)
+            as u16;
+        (0..rows)
+            .map(|row| {
+                let (origin, len, padding, alignment) =
+                    match self.orientation {
+                        Orientation::Horizontal => (
+                            Position::new(
+                                area.x,
+                                area.y
+                                    .saturating_add(offset)
+                                    .saturating_add(row),
+                            ),
+                            area.width,
+                            (p_l, p_r),
+                            self.horizontal_alignment,
+                        ),
+                        Orientation::Vertical => (
+                            Position::new(
+                                area.x
+                                    .saturating_add(offset)
+                                    .saturating_add(row),
+                                area.y,
+                            ),
+                            area.height,
+                            (p_t, p_b),
+                            self.vertical_alignment
+                                .clone()
+                                .into(),
+                        ),
+                    };
+                RuleLayout::new(
+                    self.row_set(row),
+                    self.orientation.clone(),
+                    origin,
+                    len as usize,
+                    (
+                        padding.0 as usize,
+                        padding.1 as usize,
+                    ),
+                    alignment,
+                    (self.extra_rep_1, self.extra_rep_2),
+                )
+            })
+            .collect()
+    }
+    /// the layout of the middle row of the rule rendered in `area`, the one with the label
+    /// ```rust
+    ///     // where the center symbol landed
+    ///     let center = rule.layout(area).rect(RulePart::Center);
+    /// ```
+    pub fn layout(&self, area: Rect) -> RuleLayout {
+        let mut layouts = self.layouts(area);
+        layouts.swap_remove((layouts.len() - 1) / 2)
+    }
+    /// the rule rendered on its own, `len` cells long and `thickness` rows wide
+    /// (columns for vertical rules). the area margin is left out
+    pub fn to_buffer(&self, len: u16) -> Buffer {
+        let rows = self.thickness.max(1);
+        let (width, height) = match self.orientation {
+            Orientation::Horizontal => (len, rows),
+            Orientation::Vertical => (rows, len),
+        };
+        let Margin {
+            horizontal,
+            vertical,
+        } = self.area_margin;
+        let area = Rect::new(
+            0,
+            0,
+            width.saturating_add(
+                horizontal.saturating_mul(2),
+            ),
+            height
+                .saturating_add(vertical.saturating_mul(2)),
+        );
+        let mut full = Buffer::empty(area);
+        self.render_ref(area, &mut full);
+        let inner = area.inner(self.area_margin);
+        let mut buf = Buffer::empty(Rect::new(
+            0,
+            0,
+            inner.width,
+            inner.height,
+        ));
+        for pos in inner.positions() {
+            buf[(pos.x - inner.x, pos.y - inner.y)] =
+                full[pos].clone();
+        }
+        buf
+    }
+    /// the rule `len` cells long as plain text, one line per row
+    /// ```rust
+    ///     println!("{}", Rule::default().label("Usage").to_plain_string(30));
+    /// ```
+    pub fn to_plain_string(&self, len: u16) -> String {
+        let buf = self.to_buffer(len);
+        ansi::to_plain(&buf, buf.area)
+    }
+    /// the rule `len` cells long with its colors as ANSI escapes, one line per row.
+    /// the colors are quantized to `depth`
+    pub fn to_ansi_string(
+        &self,
+        len: u16,
+        depth: ColorDepth,
+    ) -> String {
+        let buf = self.to_buffer(len);
+        ansi::to_ansi(&buf, buf.area, depth)
+    }
+    /// renders the rows laid out in `layouts`
+    fn render_layouts(
+        &self,
+        layouts: &[RuleLayout],
+        area: Rect,
+        buf: &mut Buffer,
+        phase: f32,
+        progress: Option<f32>,
+    ) {
+        let rows = layouts.len() as u16;
+        let animated;
+        let gradient =
+            match (&self.gradient, &self.animation) {
+                (Some(g), Some(a)) => {
+                    animated = a.apply(g.as_ref(), phase);
+                    Some(&animated as &dyn Gradient)
                 }
-                VerticalAlignment::Bottom => area_old
-                    .bottom()
-                    .saturating_sub(
-                        1 + p_b
-                            + self.area_margin.vertical * 2,
-                    )
-                    .saturating_add(p_t),
+                (g, _) => g.as_deref(),
+            };
+        for (row, layout) in (0..rows).zip(layouts) {
+            let solid;
+            let gradient =
+                match (gradient, self.gradient_axis) {
+                    (Some(g), GradientAxis::Across)
+                        if rows > 1
+                            && self
+                                .gradient_field
+                                .is_none() =>
+                    {
+                        let (min, max) = g.domain();
+                        solid = Solid(g.at(min
+                            + (max - min) * row as f32
+                                / (rows - 1) as f32));
+                        Some(&solid as &dyn Gradient)
+                    }
+                    (g, _) => g,
+                };
+            self.render_row(
+                row, layout, area, buf, gradient, progress,
+            );
+        }
+    }
+    /// whether the rows are drawn with `gradient` as is, so its colors can be kept between renders.
+    /// animated and per-row gradients change every render
+    fn caches_palette(&self) -> bool {
+        self.animation.is_none()
+            && (self.gradient_axis == GradientAxis::Along
+                || self.thickness.max(1) == 1)
+    }
+    /// the set of a row of a thick rule
+    fn row_set(&self, row: u16) -> &Set {
+        self.layers
+            .get(row as usize)
+            .unwrap_or(&self.symbol_set)
+    }
+    /// writes the spans (one grapheme each) straight into the cells of the row,
+    /// leaving out the ones that don't fit in `clip`
+    fn write_cells(
+        &self,
+        spans: &[Span],
+        layout: &RuleLayout,
+        clip: Rect,
+        buf: &mut Buffer,
+    ) {
+        let mut cell = 0;
+        for s in spans {
+            let width = s.content.width();
+            let pos = layout.position(cell);
+            cell += match self.orientation {
+                Orientation::Horizontal => width,
+                Orientation::Vertical => 1,
+            };
+            if width == 0
+                || s.content.contains(char::is_control)
+            {
+                continue;
             }
-            .saturating_sub(self.extra_rep_1 as u16);
-        };
-        if self.orientation == Orientation::Vertical {
-            area_old.x = match self.horizontal_alignment {
-                Alignment::Left => area_old
-                    .x
-                    .saturating_sub(p_r)
-                    .saturating_add(p_l),
-                Alignment::Center => (area_old.right() / 2)
-                    .saturating_sub(1 + p_r)
-                    .saturating_add(p_l),
-
-                Alignment::Right => {
-                    area_old.right().saturating_sub(
-                        1 + p_r
-                            + self.area_margin.horizontal
-                                * 2,
-                    )
+            let fits = pos.x as usize + width
+                <= clip.right() as usize;
+            match self.orientation {
+                Orientation::Horizontal if !fits => break,
+                Orientation::Vertical
+                    if pos.y >= clip.bottom() =>
+                {
+                    break;
+                }
+                _ if !fits || !clip.contains(pos) => {
+                    continue;
                 }
+                _ => {}
             }
-            .saturating_sub(self.extra_rep_1 as u16);
+            if let Some(c) = buf.cell_mut(pos) {
+                c.set_symbol(&s.content).set_style(s.style);
+            }
+            // the cells under the rest of a wide glyph
+            for x in pos.x + 1..pos.x + width as u16 {
+                if let Some(c) = buf.cell_mut((x, pos.y)) {
+                    c.reset();
+                }
+            }
+        }
+    }
+    /// renders one row (column for vertical rules) of the rule.
+    /// only the middle row gets the label.
+    /// `bounds` is the area the whole rule is rendered in
+    fn render_row(
+        &self,
+        row: u16,
+        layout: &RuleLayout,
+        bounds: Rect,
+        buf: &mut Buffer,
+        gradient: Option<&dyn Gradient>,
+        progress: Option<f32>,
+    ) {
+        let set = self.row_set(row);
+        let label = row == (self.thickness.max(1) - 1) / 2;
+        // nothing is drawn outside of the area
+        let clip = bounds
+            .inner(self.area_margin)
+            .intersection(buf.area);
+        let across = match self.orientation {
+            Orientation::Horizontal => (clip.top()
+                ..clip.bottom())
+                .contains(&layout.origin.y),
+            Orientation::Vertical => (clip.left()
+                ..clip.right())
+                .contains(&layout.origin.x),
         };
-
-        let area = area_old.inner(self.area_margin);
-
-        let ln = create_segment!(
-            self.symbol_set,
-            match self.orientation {
-                Orientation::Vertical => p_t,
-                Orientation::Horizontal => p_l,
-            } as usize,
-            match self.orientation {
-                Orientation::Vertical => p_b,
-                Orientation::Horizontal => p_r,
-            } as usize,
-            match self.orientation {
-                Orientation::Horizontal =>
-                    area.width as f32,
-                Orientation::Vertical => area.height as f32,
-            },
-            self.orientation,
-            self.horizontal_alignment,
-            self.vertical_alignment,
-            self.extra_rep_1,
-            self.extra_rep_2
+        if !across {
+            return;
+        }
+        let mut ln = layout.segment(set);
+        // cells that are left without the gradient in progress mode
+        let mut unfilled = None;
+        if let Some(ratio) = progress {
+            let inner = layout.track();
+            let (track, filled) = progress::track(
+                set,
+                &self.orientation,
+                inner.len(),
+                ratio,
+                self.smooth_progress,
+            );
+            ln = progress::splice(
+                &ln,
+                &self.orientation,
+                inner.clone(),
+                &track,
+            );
+            unfilled = Some(match self.orientation {
+                Orientation::Horizontal => {
+                    inner.start + filled..usize::MAX
+                }
+                Orientation::Vertical => {
+                    0..inner.end - filled
+                }
+            });
+        }
+        let bg = BgMode::from(&self.bg);
+        let cached;
+        let fresh;
+        let mut spans: Vec<Span> = if let Some(boxed) =
+            gradient
+            && let Some(field) = &self.gradient_field
+        {
+            field::paint(
+                &ln,
+                &self.orientation,
+                layout.origin,
+                boxed,
+                &self.bg,
+                field,
+                field.bounds.unwrap_or(bounds),
+            )
+        } else if let Some(boxed) = gradient {
+            let line = Line::from(ln.as_str());
+            let palette = if self.caches_palette() {
+                cached =
+                    self.cache.get(boxed, bg, line.width());
+                &*cached
+            } else {
+                fresh = text::Palette::new(
+                    boxed,
+                    bg,
+                    line.width(),
+                );
+                &fresh
+            };
+            text::paint(line, palette, bg, |cell, _| cell)
+                .spans
+        } else {
+            ln.graphemes(true).map(Span::raw).collect()
+        };
+        if let Some(unfilled) = unfilled
+            && gradient.is_some()
+        {
+            progress::clear_unfilled(
+                &mut spans,
+                &self.orientation,
+                unfilled,
+                &self.bg,
+            );
+        }
+        if !self.markers.is_empty() || self.ticks.is_some()
+        {
+            spans = marker::apply_markers(
+                &self.markers,
+                self.ticks.as_ref(),
+                &self.orientation,
+                spans,
+                layout.track(),
+            );
+        }
+        if label && self.label.is_some() {
+            spans = label::apply_label(
+                self,
+                spans,
+                layout.track(),
+                layout.center.clone(),
+            );
+        }
+        color::quantize_spans(
+            &mut spans,
+            self.color_depth,
+            self.dither,
+            &self.orientation,
         );
-
-        let ln = if let Some(boxed) = &self.gradient {
-            match self.bg {
-                Bg::None => Line::from(
-                    generate_gradient_text!(ln, boxed),
-                ),
-                _ => Line::from(generate_gradient_text!(
-                    ln, boxed, &self.bg
-                )),
-            }
+        // symbols under the rule, to join them afterwards
+        let below: Vec<(Position, String)> = if self
+            .merge_junctions
+        {
+            let len = match self.orientation {
+                Orientation::Horizontal => {
+                    spans.iter().map(Span::width).sum()
+                }
+                Orientation::Vertical => spans.len(),
+            };
+            (0..len)
+                .map(|i| layout.position(i))
+                .filter(|pos| clip.contains(*pos))
+                .filter_map(|pos| {
+                    let cell = buf.cell(pos)?;
+                    Some((pos, cell.symbol().to_string()))
+                })
+                .collect()
         } else {
-            Line::from(crate::create_raw_spans!(ln))
+            Vec::new()
         };
-        match self.orientation {
-            Orientation::Horizontal => {
-                buf.set_line(
-                    area.x,
-                    area.y,
-                    &ln,
-                    ln.spans.len() as u16 + 1,
-                );
-            }
-            Orientation::Vertical => {
-                for (y_n, s) in ln.iter().enumerate() {
-                    buf.set_span(
-                        area.x,
-                        area.y + y_n as u16,
-                        s,
-                        1,
-                    );
+        self.write_cells(&spans, layout, clip, buf);
+        // the ends of the rule don't stick out of it
+        let drawn: Vec<bool> = below
+            .iter()
+            .map(|(pos, _)| {
+                buf.cell(*pos)
+                    .is_some_and(|c| c.symbol() != " ")
+            })
+            .collect();
+        let first = drawn.iter().position(|d| *d);
+        let last = drawn.iter().rposition(|d| *d);
+        for (i, (pos, symbol)) in
+            below.into_iter().enumerate()
+        {
+            let (start, end) =
+                (Some(i) != first, Some(i) != last);
+            let keep = match self.orientation {
+                Orientation::Horizontal => {
+                    [true, end, true, start]
+                }
+                Orientation::Vertical => {
+                    [start, true, end, true]
                 }
+            };
+            if let Some(cell) = buf.cell_mut(pos)
+                && let Some(merged) =
+                    junction::merge_trimmed(
+                        &symbol,
+                        cell.symbol(),
+                        keep,
+                    )
+            {
+                cell.set_symbol(merged);
             }
         }
     }
 }
+impl WidgetRef for Rule {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        self.render_layouts(
+            &self.layouts(area),
+            area,
+            buf,
+            self.phase,
+            self.progress,
+        );
+    }
+}
+impl ratatui::widgets::StatefulWidget for Rule {
+    type State = RuleState;
+    fn render(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut RuleState,
+    ) {
+        ratatui::widgets::StatefulWidgetRef::render_ref(
+            &self, area, buf, state,
+        );
+    }
+}
+impl ratatui::widgets::StatefulWidget for &Rule {
+    type State = RuleState;
+    fn render(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut RuleState,
+    ) {
+        ratatui::widgets::StatefulWidgetRef::render_ref(
+            self, area, buf, state,
+        );
+    }
+}
+/// renders with the phase and progress of the state
+/// and keeps the area and layouts for hit testing
+impl ratatui::widgets::StatefulWidgetRef for Rule {
+    type State = RuleState;
+    fn render_ref(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut RuleState,
+    ) {
+        let (phase, progress) =
+            (state.phase, state.progress.or(self.progress));
+        let layouts = state.layouts_for(self, area);
+        self.render_layouts(
+            layouts, area, buf, phase, progress,
+        );
+    }
+}
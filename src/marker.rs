@@ -0,0 +1,309 @@
+//! ### Markers
+//! glyphs put on chosen cells of the rule, like a split point or the ticks of a scale.
+//! positions are counted over the track, the cells between the start and end symbols
+//! ```rust
+//!     // `├─────┼──────────────┤` split at 30%
+//!     Rule::default()
+//!         .start("├")
+//!         .end("┤")
+//!         .marker(Marker::ratio(0.3, "┼"))
+//!     // a scale with a tick every 2 cells and a major one every 10
+//!     Rule::default().ticks(Ticks::new(2, "┴").major(10, "┼"))
+//! ```
+use crate::{Orientation, label};
+use ratatui::{style::Style, text::Span};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, ops::Range};
+use unicode_segmentation::UnicodeSegmentation;
+/// where a marker goes on the track
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarkerPosition {
+    /// from 0 (the first cell of the track) to 1 (the last one)
+    Ratio(f32),
+    /// cells from the first cell of the track
+    Cell(usize),
+    /// cells from the last cell of the track
+    FromEnd(usize),
+}
+impl MarkerPosition {
+    /// the cell of a track `len` cells long at this position.
+    /// `None` when it's off the track
+    pub fn cell(&self, len: usize) -> Option<usize> {
+        let cell = match *self {
+            MarkerPosition::Ratio(ratio) => {
+                if len == 0 || ratio.is_nan() {
+                    return None;
+                }
+                (ratio.clamp(0.0, 1.0) * (len - 1) as f32)
+                    .round() as usize
+            }
+            MarkerPosition::Cell(cell) => cell,
+            MarkerPosition::FromEnd(cell) => {
+                len.checked_sub(cell + 1)?
+            }
+        };
+        (cell < len).then_some(cell)
+    }
+}
+/// a glyph at one position of the rule
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Marker {
+    pub position: MarkerPosition,
+    pub symbol: Cow<'static, str>,
+    /// applied on top of the rule's style at the marker's cell
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub style: Style,
+}
+impl Marker {
+    pub fn new(
+        position: MarkerPosition,
+        symbol: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            position,
+            symbol: symbol.into(),
+            style: Style::new(),
+        }
+    }
+    /// a marker at `ratio` (0 to 1) of the track
+    pub fn ratio(
+        ratio: f32,
+        symbol: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self::new(MarkerPosition::Ratio(ratio), symbol)
+    }
+    /// a marker `cell` cells after the start symbol
+    pub fn at(
+        cell: usize,
+        symbol: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self::new(MarkerPosition::Cell(cell), symbol)
+    }
+    /// a marker `cell` cells before the end symbol
+    pub fn from_end(
+        cell: usize,
+        symbol: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self::new(MarkerPosition::FromEnd(cell), symbol)
+    }
+    pub fn style<S: Into<Style>>(
+        mut self,
+        style: S,
+    ) -> Self {
+        self.style = style.into();
+        self
+    }
+}
+/// evenly spaced marks along the whole track, like a ruler.
+/// counted from the first cell of the track
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize)
+)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ticks {
+    /// a tick every `every` cells. 0 turns the minor ticks off
+    pub every: usize,
+    pub symbol: Cow<'static, str>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub style: Style,
+    /// a major tick every `major_every` cells instead of a minor one. 0 turns them off
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub major_every: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub major_symbol: Cow<'static, str>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub major_style: Style,
+}
+impl Ticks {
+    /// a tick every `every` cells
+    pub fn new(
+        every: usize,
+        symbol: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let symbol = symbol.into();
+        Self {
+            every,
+            major_symbol: symbol.clone(),
+            symbol,
+            style: Style::new(),
+            major_every: 0,
+            major_style: Style::new(),
+        }
+    }
+    /// a major tick every `every` cells
+    pub fn major(
+        mut self,
+        every: usize,
+        symbol: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.major_every = every;
+        self.major_symbol = symbol.into();
+        self
+    }
+    pub fn style<S: Into<Style>>(
+        mut self,
+        style: S,
+    ) -> Self {
+        self.style = style.into();
+        self
+    }
+    pub fn major_style<S: Into<Style>>(
+        mut self,
+        style: S,
+    ) -> Self {
+        self.major_style = style.into();
+        self
+    }
+    /// the ticks on a track `len` cells long, as markers
+    pub fn markers(&self, len: usize) -> Vec<Marker> {
+        (0..len)
+            .filter_map(|cell| {
+                let (symbol, style) = self.at(cell)?;
+                Some(
+                    Marker::at(cell, symbol.clone())
+                        .style(style),
+                )
+            })
+            .collect()
+    }
+    /// the symbol and style of the tick on `cell` of the track, if there is one
+    fn at(
+        &self,
+        cell: usize,
+    ) -> Option<(&Cow<'static, str>, Style)> {
+        let on = |every: usize| {
+            every > 0 && cell.is_multiple_of(every)
+        };
+        if on(self.major_every) {
+            Some((&self.major_symbol, self.major_style))
+        } else if on(self.every) {
+            Some((&self.symbol, self.style))
+        } else {
+            None
+        }
+    }
+}
+/// puts the ticks and then the markers of the rule onto the rendered spans (one span per grapheme).
+/// `track` is the range of cells between the start and end symbols.
+/// markers that don't fit on the track are left out
+pub(crate) fn apply_markers<'a>(
+    markers: &[Marker],
+    ticks: Option<&Ticks>,
+    o: &Orientation,
+    mut spans: Vec<Span<'a>>,
+    track: Range<usize>,
+) -> Vec<Span<'a>> {
+    if let Some(ticks) = ticks {
+        spans = apply_ticks(ticks, o, spans, track.clone());
+    }
+    for marker in markers {
+        let Some(cell) = marker.position.cell(track.len())
+        else {
+            continue;
+        };
+        let start = track.start + cell;
+        let end = start + o.symbol_len(&marker.symbol);
+        if end == start || end > track.end {
+            continue;
+        }
+        let style =
+            style_at(&spans, o, start).patch(marker.style);
+        spans = label::overlay(
+            spans,
+            graphemes(&marker.symbol, style),
+            start..end,
+            o,
+        );
+    }
+    spans
+}
+/// puts the ticks onto the spans in one pass over the row.
+/// a tick takes the style of the span it starts on and
+/// the ticks starting under a longer tick before them are left out
+fn apply_ticks<'a>(
+    ticks: &Ticks,
+    o: &Orientation,
+    spans: Vec<Span<'a>>,
+    track: Range<usize>,
+) -> Vec<Span<'a>> {
+    let mut out: Vec<Span<'a>> =
+        Vec::with_capacity(spans.len());
+    let mut cell = 0;
+    // the cells before this one are taken by the last tick
+    let mut covered = 0;
+    for span in spans {
+        let end = cell + o.symbol_len(&span.content);
+        let mut pos = cell.max(covered);
+        let mut placed = Vec::new();
+        for t in pos.max(track.start)..end.min(track.end) {
+            let Some((symbol, style)) =
+                ticks.at(t - track.start)
+            else {
+                continue;
+            };
+            let len = o.symbol_len(symbol);
+            if t < pos || len == 0 || t + len > track.end {
+                continue;
+            }
+            placed.extend(blanks(pos..t, span.style));
+            placed.extend(graphemes(
+                symbol,
+                span.style.patch(style),
+            ));
+            pos = t + len;
+            covered = pos;
+        }
+        if pos == cell {
+            out.push(span);
+        } else {
+            out.extend(placed);
+            // the rest of a wide span a tick started on
+            out.extend(blanks(pos..end, span.style));
+        }
+        cell = end;
+    }
+    out
+}
+/// one span per grapheme, like the rest of the rule
+fn graphemes<'a>(
+    symbol: &str,
+    style: Style,
+) -> Vec<Span<'a>> {
+    symbol
+        .graphemes(true)
+        .map(|g| Span::styled(g.to_string(), style))
+        .collect()
+}
+/// a space for every cell of `cells`
+fn blanks<'a>(
+    cells: Range<usize>,
+    style: Style,
+) -> impl Iterator<Item = Span<'a>> {
+    cells.map(move |_| Span::styled(" ", style))
+}
+/// the style of the span covering `cell`
+fn style_at(
+    spans: &[Span],
+    o: &Orientation,
+    cell: usize,
+) -> Style {
+    let mut end = 0;
+    spans
+        .iter()
+        .find(|s| {
+            end += o.symbol_len(&s.content);
+            end > cell
+        })
+        .map(|s| s.style)
+        .unwrap_or_default()
+}
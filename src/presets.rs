@@ -1,237 +1,355 @@
 #![allow(dead_code)]
 pub mod horizontal {
     use crate::Set;
+    use std::borrow::Cow;
     /// `+---+---+`
     /// inspired by Spectre.Console's ASCII table border
     pub const ASCII: Set = Set {
-        start: '+',
-        rep_1: '-',
-        center: '+',
-        rep_2: '-',
-        end: '+',
+        start: Cow::Borrowed("+"),
+        rep_1: Cow::Borrowed("-"),
+        center: Cow::Borrowed("+"),
+        rep_2: Cow::Borrowed("-"),
+        end: Cow::Borrowed("+"),
     };
     /// `⠺⠿⠿⠿⠿⠿⠿⠿⠗`
     pub const BRAILLE_3_POINTED_FILLED: Set = Set {
-        start: '⠺',
-        rep_1: '⠿',
-        center: '⠿',
-        rep_2: '⠿',
-        end: '⠗',
+        start: Cow::Borrowed("⠺"),
+        rep_1: Cow::Borrowed("⠿"),
+        center: Cow::Borrowed("⠿"),
+        rep_2: Cow::Borrowed("⠿"),
+        end: Cow::Borrowed("⠗"),
     };
     /// `⠪⠭⠭⠭⠭⠭⠭⠭⠕`
     pub const BRAILLE_3_POINTED_HOLLOW: Set = Set {
-        start: '⠪',
-        rep_1: '⠭',
-        center: '⠭',
-        rep_2: '⠭',
-        end: '⠕',
+        start: Cow::Borrowed("⠪"),
+        rep_1: Cow::Borrowed("⠭"),
+        center: Cow::Borrowed("⠭"),
+        rep_2: Cow::Borrowed("⠭"),
+        end: Cow::Borrowed("⠕"),
     };
     /// `⠮⠭⠭⠭⠭⠭⠭⠭⠵`
     pub const BRAILLE_3_UP_HOLLOW: Set = Set {
-        start: '⠮',
-        rep_1: '⠭',
-        center: '⠭',
-        rep_2: '⠭',
-        end: '⠵',
+        start: Cow::Borrowed("⠮"),
+        rep_1: Cow::Borrowed("⠭"),
+        center: Cow::Borrowed("⠭"),
+        rep_2: Cow::Borrowed("⠭"),
+        end: Cow::Borrowed("⠵"),
     };
     /// `⠾⠿⠿⠿⠿⠿⠿⠿⠷`
     pub const BRAILLE_3_UP_FILLED: Set = Set {
-        start: '⠾',
-        rep_1: '⠿',
-        center: '⠿',
-        rep_2: '⠿',
-        end: '⠷',
+        start: Cow::Borrowed("⠾"),
+        rep_1: Cow::Borrowed("⠿"),
+        center: Cow::Borrowed("⠿"),
+        rep_2: Cow::Borrowed("⠿"),
+        end: Cow::Borrowed("⠷"),
     };
     /// `⠻⠿⠿⠿⠿⠿⠿⠿⠟`
     pub const BRAILLE_3_DOWN_FILLED: Set = Set {
-        start: '⠻',
-        rep_1: '⠿',
-        center: '⠿',
-        rep_2: '⠿',
-        end: '⠟',
+        start: Cow::Borrowed("⠻"),
+        rep_1: Cow::Borrowed("⠿"),
+        center: Cow::Borrowed("⠿"),
+        rep_2: Cow::Borrowed("⠿"),
+        end: Cow::Borrowed("⠟"),
     };
     /// `⠫⠭⠭⠭⠭⠭⠭⠭⠝`
     pub const BRAILLE_3_DOWN_HOLLOW: Set = Set {
-        start: '⠫',
-        rep_1: '⠭',
-        center: '⠭',
-        rep_2: '⠭',
-        end: '⠝',
+        start: Cow::Borrowed("⠫"),
+        rep_1: Cow::Borrowed("⠭"),
+        center: Cow::Borrowed("⠭"),
+        rep_2: Cow::Borrowed("⠭"),
+        end: Cow::Borrowed("⠝"),
     };
     /// `⠻⠿⠿⠿⠿⠿⠿⠿⠷`
     pub const BRAILLE_3_PARALLELOGRAM_LEFT_FILLED: Set =
         Set {
-            start: '⠻',
-            rep_1: '⠿',
-            center: '⠿',
-            rep_2: '⠿',
-            end: '⠷',
+            start: Cow::Borrowed("⠻"),
+            rep_1: Cow::Borrowed("⠿"),
+            center: Cow::Borrowed("⠿"),
+            rep_2: Cow::Borrowed("⠿"),
+            end: Cow::Borrowed("⠷"),
         };
     /// `⠫⠭⠭⠭⠭⠭⠭⠭⠵`
     pub const BRAILLE_3_PARALLELOGRAM_LEFT_HOLLOW: Set =
         Set {
-            start: '⠫',
-            rep_1: '⠭',
-            center: '⠭',
-            rep_2: '⠭',
-            end: '⠵',
+            start: Cow::Borrowed("⠫"),
+            rep_1: Cow::Borrowed("⠭"),
+            center: Cow::Borrowed("⠭"),
+            rep_2: Cow::Borrowed("⠭"),
+            end: Cow::Borrowed("⠵"),
         };
     /// `⠾⠿⠿⠿⠿⠿⠿⠿⠟`
     pub const BRAILLE_3_PARALLELOGRAM_RIGHT_FILLED: Set =
         Set {
-            start: '⠾',
-            rep_1: '⠿',
-            center: '⠿',
-            rep_2: '⠿',
-            end: '⠟',
+            start: Cow::Borrowed("⠾"),
+            rep_1: Cow::Borrowed("⠿"),
+            center: Cow::Borrowed("⠿"),
+            rep_2: Cow::Borrowed("⠿"),
+            end: Cow::Borrowed("⠟"),
         };
     /// `⠮⠭⠭⠭⠭⠭⠭⠭⠝`
     pub const BRAILLE_3_PARALLELOGRAM_RIGHT_HOLLOW: Set =
         Set {
-            start: '⠮',
-            rep_1: '⠭',
-            center: '⠭',
-            rep_2: '⠭',
-            end: '⠝',
+            start: Cow::Borrowed("⠮"),
+            rep_1: Cow::Borrowed("⠭"),
+            center: Cow::Borrowed("⠭"),
+            rep_2: Cow::Borrowed("⠭"),
+            end: Cow::Borrowed("⠝"),
         };
 }
 pub mod vertical {
     pub use crate::Set;
+    use std::borrow::Cow;
     pub const ASCII: Set = Set {
-        start: '+',
-        rep_1: '|',
-        center: '+',
-        rep_2: '|',
-        end: '+',
+        start: Cow::Borrowed("+"),
+        rep_1: Cow::Borrowed("|"),
+        center: Cow::Borrowed("+"),
+        rep_2: Cow::Borrowed("|"),
+        end: Cow::Borrowed("+"),
     };
 }
+/// generates the `TOP`, `BOTTOM`, `LEFT` and `RIGHT` sets of a border family.
+/// with `tees`, also the `HORIZONTAL_TEE`, `VERTICAL_TEE`, `HORIZONTAL_CROSS`
+/// and `VERTICAL_CROSS` sets for rules that join a border
+macro_rules! border_family {
+    (
+        $(#[$meta:meta])*
+        $family:ident {
+            top: $top:literal,
+            bottom: $bottom:literal,
+            left: $left:literal,
+            right: $right:literal,
+            corners: [$tl:literal, $tr:literal, $bl:literal, $br:literal]
+            $(, tees: [$lt:literal, $rt:literal, $tt:literal, $bt:literal, $cross:literal])?
+            $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        pub mod $family {
+            use crate::Set;
+            use std::borrow::Cow;
+            #[doc = concat!("`", $tl, $top, $top, $top, $tr, "`")]
+            pub const TOP: Set = border_family!(@set $tl, $top, $top, $tr);
+            #[doc = concat!("`", $bl, $bottom, $bottom, $bottom, $br, "`")]
+            pub const BOTTOM: Set = border_family!(@set $bl, $bottom, $bottom, $br);
+            #[doc = concat!("`", $tl, $left, $left, $left, $bl, "` (top to bottom)")]
+            pub const LEFT: Set = border_family!(@set $tl, $left, $left, $bl);
+            #[doc = concat!("`", $tr, $right, $right, $right, $br, "` (top to bottom)")]
+            pub const RIGHT: Set = border_family!(@set $tr, $right, $right, $br);
+            $(
+                /// a horizontal rule that joins the left and right borders
+                #[doc = concat!("`", $lt, $top, $top, $top, $rt, "`")]
+                pub const HORIZONTAL_TEE: Set = border_family!(@set $lt, $top, $top, $rt);
+                /// a vertical rule that joins the top and bottom borders
+                #[doc = concat!("`", $tt, $left, $left, $left, $bt, "` (top to bottom)")]
+                pub const VERTICAL_TEE: Set = border_family!(@set $tt, $left, $left, $bt);
+                /// `HORIZONTAL_TEE` crossing a vertical rule in the middle
+                #[doc = concat!("`", $lt, $top, $cross, $top, $rt, "`")]
+                pub const HORIZONTAL_CROSS: Set = border_family!(@set $lt, $top, $cross, $rt);
+                /// `VERTICAL_TEE` crossing a horizontal rule in the middle
+                #[doc = concat!("`", $tt, $left, $cross, $left, $bt, "` (top to bottom)")]
+                pub const VERTICAL_CROSS: Set = border_family!(@set $tt, $left, $cross, $bt);
+            )?
+        }
+    };
+    (@set $start:literal, $rep:literal, $center:literal, $end:literal) => {
+        Set {
+            start: Cow::Borrowed($start),
+            rep_1: Cow::Borrowed($rep),
+            center: Cow::Borrowed($center),
+            rep_2: Cow::Borrowed($rep),
+            end: Cow::Borrowed($end),
+        }
+    };
+}
+/// sets for drawing boxes out of rules, one module per border family
 pub mod borders {
-    pub mod plain {
-        use crate::Set;
-        pub const RIGHT: Set = Set {
-            start: '┐',
-            rep_1: '│',
-            center: '│',
-            rep_2: '│',
-            end: '┘',
-        };
-        pub const LEFT: Set = Set {
-            start: '┌',
-            rep_1: '│',
-            center: '│',
-            rep_2: '│',
-            end: '└',
-        };
-        pub const TOP: Set = Set {
-            start: '┌',
-            rep_1: '─',
-            center: '─',
-            rep_2: '─',
-            end: '┐',
-        };
-        pub const BOTTOM: Set = Set {
-            start: '└',
-            rep_1: '─',
-            center: '─',
-            rep_2: '─',
-            end: '┘',
-        };
+    border_family! {
+        plain {
+            top: "─",
+            bottom: "─",
+            left: "│",
+            right: "│",
+            corners: ["┌", "┐", "└", "┘"],
+            tees: ["├", "┤", "┬", "┴", "┼"],
+        }
     }
-    pub mod double {
-        use crate::Set;
-
-        pub const RIGHT: Set = Set {
-            start: '╗',
-            rep_1: '║',
-            center: '║',
-            rep_2: '║',
-            end: '╝',
-        };
-
-        pub const LEFT: Set = Set {
-            start: '╔',
-            rep_1: '║',
-            center: '║',
-            rep_2: '║',
-            end: '╚',
-        };
-
-        pub const TOP: Set = Set {
-            start: '╔',
-            rep_1: '═',
-            center: '═',
-            rep_2: '═',
-            end: '╗',
-        };
-
-        pub const BOTTOM: Set = Set {
-            start: '╚',
-            rep_1: '═',
-            center: '═',
-            rep_2: '═',
-            end: '╝',
-        };
+    border_family! {
+        double {
+            top: "═",
+            bottom: "═",
+            left: "║",
+            right: "║",
+            corners: ["╔", "╗", "╚", "╝"],
+            tees: ["╠", "╣", "╦", "╩", "╬"],
+        }
     }
-    pub mod rounded {
-        use crate::Set;
-
-        pub const RIGHT: Set = Set {
-            start: '╮',
-            rep_1: '│',
-            center: '│',
-            rep_2: '│',
-            end: '╯',
-        };
-
-        pub const LEFT: Set = Set {
-            start: '╭',
-            rep_1: '│',
-            center: '│',
-            rep_2: '│',
-            end: '╰',
-        };
-
-        pub const TOP: Set = Set {
-            start: '╭',
-            rep_1: '─',
-            center: '─',
-            rep_2: '─',
-            end: '╮',
-        };
-
-        pub const BOTTOM: Set = Set {
-            start: '╰',
-            rep_1: '─',
-            center: '─',
-            rep_2: '─',
-            end: '╯',
+    border_family! {
+        rounded {
+            top: "─",
+            bottom: "─",
+            left: "│",
+            right: "│",
+            corners: ["╭", "╮", "╰", "╯"],
+            tees: ["├", "┤", "┬", "┴", "┼"],
+        }
+    }
+    border_family! {
+        thick {
+            top: "━",
+            bottom: "━",
+            left: "┃",
+            right: "┃",
+            corners: ["┏", "┓", "┗", "┛"],
+            tees: ["┣", "┫", "┳", "┻", "╋"],
+        }
+    }
+    border_family! {
+        /// light triple dashes
+        dashed {
+            top: "┄",
+            bottom: "┄",
+            left: "┆",
+            right: "┆",
+            corners: ["┌", "┐", "└", "┘"],
+            tees: ["├", "┤", "┬", "┴", "┼"],
+        }
+    }
+    border_family! {
+        /// light quadruple dashes
+        dotted {
+            top: "┈",
+            bottom: "┈",
+            left: "┊",
+            right: "┊",
+            corners: ["┌", "┐", "└", "┘"],
+            tees: ["├", "┤", "┬", "┴", "┼"],
+        }
+    }
+    border_family! {
+        /// heavy triple dashes
+        heavy_dashed {
+            top: "┅",
+            bottom: "┅",
+            left: "┇",
+            right: "┇",
+            corners: ["┏", "┓", "┗", "┛"],
+            tees: ["┣", "┫", "┳", "┻", "╋"],
+        }
+    }
+    border_family! {
+        /// heavy horizontal lines, light vertical lines
+        heavy_horizontal {
+            top: "━",
+            bottom: "━",
+            left: "│",
+            right: "│",
+            corners: ["┍", "┑", "┕", "┙"],
+            tees: ["┝", "┥", "┯", "┷", "┿"],
+        }
+    }
+    border_family! {
+        /// light horizontal lines, heavy vertical lines
+        heavy_vertical {
+            top: "─",
+            bottom: "─",
+            left: "┃",
+            right: "┃",
+            corners: ["┎", "┒", "┖", "┚"],
+            tees: ["┠", "┨", "┰", "┸", "╂"],
+        }
+    }
+    border_family! {
+        /// half blocks drawn outside the content, like `BorderType::QuadrantOutside`
+        quadrant {
+            top: "▀",
+            bottom: "▄",
+            left: "▌",
+            right: "▐",
+            corners: ["▛", "▜", "▙", "▟"],
+        }
+    }
+    border_family! {
+        ascii {
+            top: "-",
+            bottom: "-",
+            left: "|",
+            right: "|",
+            corners: ["+", "+", "+", "+"],
+            tees: ["+", "+", "+", "+", "+"],
+        }
+    }
+}
+/// sets for `Rule::progress`
+pub mod progress {
+    use crate::Set;
+    use std::borrow::Cow;
+    /// `████▌   ` with `Rule::smooth_progress`
+    pub const BLOCK: Set = Set {
+        start: Cow::Borrowed(""),
+        rep_1: Cow::Borrowed("█"),
+        center: Cow::Borrowed("█"),
+        rep_2: Cow::Borrowed(" "),
+        end: Cow::Borrowed(""),
+    };
+    /// `━━━━╸───`
+    pub const LINE: Set = Set {
+        start: Cow::Borrowed(""),
+        rep_1: Cow::Borrowed("━"),
+        center: Cow::Borrowed("╸"),
+        rep_2: Cow::Borrowed("─"),
+        end: Cow::Borrowed(""),
+    };
+}
+/// half blocks for the rows of thick rules (`Rule::layers`)
+pub mod bands {
+    use crate::Set;
+    use std::borrow::Cow;
+    macro_rules! band {
+        ($symb:literal) => {
+            Set {
+                start: Cow::Borrowed($symb),
+                rep_1: Cow::Borrowed($symb),
+                center: Cow::Borrowed($symb),
+                rep_2: Cow::Borrowed($symb),
+                end: Cow::Borrowed($symb),
+            }
         };
     }
+    /// `▀▀▀▀▀▀`
+    pub const UPPER: Set = band!("▀");
+    /// `▄▄▄▄▄▄`
+    pub const LOWER: Set = band!("▄");
+    /// `▌`, for vertical rules
+    pub const LEFT: Set = band!("▌");
+    /// `▐`, for vertical rules
+    pub const RIGHT: Set = band!("▐");
 }
 pub mod neutral {
     pub use crate::Set;
+    use std::borrow::Cow;
     pub const EMPTY: Set = Set {
-        start: ' ',
-        rep_1: ' ',
-        center: ' ',
-        rep_2: ' ',
-        end: ' ',
+        start: Cow::Borrowed(" "),
+        rep_1: Cow::Borrowed(" "),
+        center: Cow::Borrowed(" "),
+        rep_2: Cow::Borrowed(" "),
+        end: Cow::Borrowed(" "),
     };
 }
 pub mod test_sets {
     pub use crate::Set;
+    use std::borrow::Cow;
     pub const VERTICAL: Set = Set {
-        start: '+',
-        rep_1: '│',
-        center: '+',
-        rep_2: '│',
-        end: '+',
+        start: Cow::Borrowed("+"),
+        rep_1: Cow::Borrowed("│"),
+        center: Cow::Borrowed("+"),
+        rep_2: Cow::Borrowed("│"),
+        end: Cow::Borrowed("+"),
     };
     pub const HORIZONTAL: Set = Set {
-        start: '+',
-        rep_1: '─',
-        center: '+',
-        rep_2: '─',
-        end: '+',
+        start: Cow::Borrowed("+"),
+        rep_1: Cow::Borrowed("─"),
+        center: Cow::Borrowed("+"),
+        rep_2: Cow::Borrowed("─"),
+        end: Cow::Borrowed("+"),
     };
 }
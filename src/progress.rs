@@ -0,0 +1,111 @@
+//! progress-bar mode of `Rule`.
+//! the cells between the start and end symbols are the track:
+//! `rep_1` is the filled part, `center` the head and `rep_2` the rest.
+//! horizontal bars fill from left to right, vertical ones from the bottom up
+use crate::{Bg, Orientation, Set};
+use ratatui::{style::Style, text::Span};
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+/// left eighths, from 1/8 to 7/8
+const HORIZONTAL_EIGHTHS: [&str; 7] =
+    ["▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+/// lower eighths, from 1/8 to 7/8
+const VERTICAL_EIGHTHS: [&str; 7] =
+    ["▁", "▂", "▃", "▄", "▅", "▆", "▇"];
+/// the track for `ratio`, exactly `len` cells long,
+/// and the number of cells that are filled (including the head)
+pub(crate) fn track(
+    set: &Set,
+    o: &Orientation,
+    len: usize,
+    ratio: f32,
+    smooth: bool,
+) -> (String, usize) {
+    let ratio = if ratio.is_nan() {
+        0.0
+    } else {
+        ratio.clamp(0.0, 1.0)
+    };
+    let (filled, head, filled_len) = if smooth {
+        let eighths =
+            (ratio * len as f32 * 8.0).round() as usize;
+        let (full, part) = (eighths / 8, eighths % 8);
+        let head = match (part, o) {
+            (0, _) => "",
+            (_, Orientation::Horizontal) => {
+                HORIZONTAL_EIGHTHS[part - 1]
+            }
+            (_, Orientation::Vertical) => {
+                VERTICAL_EIGHTHS[part - 1]
+            }
+        };
+        (
+            o.tile(&set.rep_1, full),
+            head,
+            full + (part > 0) as usize,
+        )
+    } else {
+        let n = (ratio * len as f32).round() as usize;
+        let center_len = o.symbol_len(&set.center);
+        // a full bar has no head
+        if n == len || n < center_len {
+            (o.tile(&set.rep_1, n), "", n)
+        } else {
+            (
+                o.tile(&set.rep_1, n - center_len),
+                set.center.as_ref(),
+                n,
+            )
+        }
+    };
+    let rest = o.tile(&set.rep_2, len - filled_len);
+    let track = match o {
+        Orientation::Horizontal => filled + head + &rest,
+        Orientation::Vertical => rest + head + &filled,
+    };
+    (track, filled_len)
+}
+/// replaces the cells in `range` of the segment with `track`
+pub(crate) fn splice(
+    segment: &str,
+    o: &Orientation,
+    range: Range<usize>,
+    track: &str,
+) -> String {
+    let mut out = String::with_capacity(segment.len());
+    let mut cell = 0;
+    let mut track = Some(track);
+    for g in segment.graphemes(true) {
+        if cell >= range.start
+            && let Some(track) = track.take()
+        {
+            out.push_str(track);
+        }
+        if cell < range.start || cell >= range.end {
+            out.push_str(g);
+        }
+        cell += o.symbol_len(g);
+    }
+    if let Some(track) = track {
+        out.push_str(track);
+    }
+    out
+}
+/// removes the gradient from the cells in `unfilled`. a solid bg stays
+pub(crate) fn clear_unfilled(
+    spans: &mut [Span],
+    o: &Orientation,
+    unfilled: Range<usize>,
+    bg: &Bg,
+) {
+    let mut cell = 0;
+    for s in spans {
+        if unfilled.contains(&cell) {
+            s.style = match bg {
+                Bg::Solid(c) => Style::new().bg(*c),
+                _ => Style::new(),
+            };
+        }
+        cell += o.symbol_len(&s.content);
+    }
+}
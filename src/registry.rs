@@ -0,0 +1,329 @@
+//! ### Named preset registry
+//! looks up `Set`s by their path in `presets`, so rule styles can be picked at runtime
+//! ```rust
+//!     let set = registry::get("borders::double::TOP").unwrap();
+//!     registry::register("myapp::fancy", Orientation::Horizontal, my_set);
+//!     for info in registry::all() {
+//!         println!("{:<40} {}", info.name, info.preview(12));
+//!     }
+//! ```
+use crate::{Orientation, RuleLayout, Set, presets};
+use ratatui::layout::{Alignment, Position};
+use std::{
+    borrow::Cow,
+    sync::{LazyLock, RwLock},
+};
+/// a named `Set` and the orientation it's meant for
+#[derive(Clone, Debug)]
+pub struct PresetInfo {
+    /// path of the preset, e.g. `"borders::double::TOP"`
+    pub name: Cow<'static, str>,
+    pub orientation: Orientation,
+    pub set: Set,
+}
+impl PresetInfo {
+    /// everything before the last `::` of the name, e.g. `"borders::double"`
+    pub fn category(&self) -> &str {
+        self.name
+            .rsplit_once("::")
+            .map_or("", |(category, _)| category)
+    }
+    /// the set rendered `len` cells long.
+    /// vertical presets have one grapheme per line
+    pub fn preview(&self, len: usize) -> String {
+        let ln = RuleLayout::new(
+            &self.set,
+            self.orientation.clone(),
+            Position::ORIGIN,
+            len,
+            (0, 0),
+            Alignment::Center,
+            (0, 0),
+        )
+        .segment(&self.set);
+        match self.orientation {
+            Orientation::Horizontal => ln,
+            Orientation::Vertical => {
+                crate::create_raw_spans!(ln)
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+    }
+}
+/// a list of named sets. starts with every built-in preset
+#[derive(Clone, Debug)]
+pub struct Registry {
+    presets: Vec<PresetInfo>,
+}
+macro_rules! builtin {
+    (@info ($($module:ident)::+) $orientation:ident $name:ident) => {
+        PresetInfo {
+            name: Cow::Borrowed(concat!(
+                $(stringify!($module), "::",)+
+                stringify!($name)
+            )),
+            orientation: Orientation::$orientation,
+            set: presets::$($module)::+::$name,
+        }
+    };
+    ($($module:tt => $orientation:ident [$($name:ident),* $(,)?];)*) => {
+        vec![$($(
+            builtin!(@info $module $orientation $name),
+        )*)*]
+    };
+}
+impl Default for Registry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+impl Registry {
+    /// a registry with every preset from the `presets` module
+    pub fn builtin() -> Self {
+        Self {
+            presets: builtin! {
+                (horizontal) => Horizontal [
+                    ASCII,
+                    BRAILLE_3_POINTED_FILLED,
+                    BRAILLE_3_POINTED_HOLLOW,
+                    BRAILLE_3_UP_HOLLOW,
+                    BRAILLE_3_UP_FILLED,
+                    BRAILLE_3_DOWN_FILLED,
+                    BRAILLE_3_DOWN_HOLLOW,
+                    BRAILLE_3_PARALLELOGRAM_LEFT_FILLED,
+                    BRAILLE_3_PARALLELOGRAM_LEFT_HOLLOW,
+                    BRAILLE_3_PARALLELOGRAM_RIGHT_FILLED,
+                    BRAILLE_3_PARALLELOGRAM_RIGHT_HOLLOW,
+                ];
+                (vertical) => Vertical [ASCII];
+                (borders::plain) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::plain) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::double) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::double) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::rounded) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::rounded) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::thick) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::thick) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::dashed) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::dashed) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::dotted) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::dotted) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::heavy_dashed) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::heavy_dashed) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::heavy_horizontal) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::heavy_horizontal) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::heavy_vertical) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::heavy_vertical) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::ascii) => Horizontal [
+                    TOP,
+                    BOTTOM,
+                    HORIZONTAL_TEE,
+                    HORIZONTAL_CROSS,
+                ];
+                (borders::ascii) => Vertical [
+                    LEFT,
+                    RIGHT,
+                    VERTICAL_TEE,
+                    VERTICAL_CROSS,
+                ];
+                (borders::quadrant) => Horizontal [TOP, BOTTOM];
+                (borders::quadrant) => Vertical [LEFT, RIGHT];
+                (progress) => Horizontal [BLOCK, LINE];
+                (bands) => Horizontal [UPPER, LOWER];
+                (bands) => Vertical [LEFT, RIGHT];
+                (neutral) => Horizontal [EMPTY];
+                (test_sets) => Horizontal [HORIZONTAL];
+                (test_sets) => Vertical [VERTICAL];
+            },
+        }
+    }
+    /// a registry without any presets
+    pub fn empty() -> Self {
+        Self {
+            presets: Vec::new(),
+        }
+    }
+    pub fn get(&self, name: &str) -> Option<&Set> {
+        self.info(name).map(|info| &info.set)
+    }
+    pub fn info(&self, name: &str) -> Option<&PresetInfo> {
+        self.presets.iter().find(|info| info.name == name)
+    }
+    /// adds a set, replacing any preset with the same name
+    pub fn register<N: Into<Cow<'static, str>>>(
+        &mut self,
+        name: N,
+        orientation: Orientation,
+        set: Set,
+    ) {
+        let info = PresetInfo {
+            name: name.into(),
+            orientation,
+            set,
+        };
+        match self
+            .presets
+            .iter_mut()
+            .find(|i| i.name == info.name)
+        {
+            Some(old) => *old = info,
+            None => self.presets.push(info),
+        }
+    }
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = &PresetInfo> {
+        self.presets.iter()
+    }
+    /// presets whose category is `category` or nested inside it
+    /// (`"borders"` also lists `"borders::double"`)
+    pub fn category<'a>(
+        &'a self,
+        category: &'a str,
+    ) -> impl Iterator<Item = &'a PresetInfo> {
+        self.presets.iter().filter(move |info| {
+            let c = info.category();
+            c == category
+                || c.strip_prefix(category).is_some_and(
+                    |rest| rest.starts_with("::"),
+                )
+        })
+    }
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.iter().map(|info| info.name.as_ref())
+    }
+}
+static GLOBAL: LazyLock<RwLock<Registry>> =
+    LazyLock::new(|| RwLock::new(Registry::builtin()));
+/// looks up a set in the global registry
+/// ```rust
+///     let top = registry::get("borders::rounded::TOP").unwrap();
+/// ```
+pub fn get(name: &str) -> Option<Set> {
+    GLOBAL
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+        .cloned()
+}
+/// looks up a preset and its orientation in the global registry
+pub fn info(name: &str) -> Option<PresetInfo> {
+    GLOBAL
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .info(name)
+        .cloned()
+}
+/// adds a set to the global registry, replacing any preset with the same name
+pub fn register<N: Into<Cow<'static, str>>>(
+    name: N,
+    orientation: Orientation,
+    set: Set,
+) {
+    GLOBAL
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(name, orientation, set);
+}
+/// every preset in the global registry
+pub fn all() -> Vec<PresetInfo> {
+    GLOBAL
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,374 @@
+//! serde support for `Rule` and `Bg`.
+//! gradients are written as `GradientSpec`s and rebuilt when deserializing
+use crate::{
+    Animation, Bg, ColorDepth, GradientAxis, GradientField,
+    GradientSpec, LabelPosition, LabelStyle, Marker,
+    Orientation, Rule, Set, Ticks, VerticalAlignment,
+    registry,
+};
+use ratatui::{
+    layout::{Alignment, Margin},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Padding,
+};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer, de,
+    ser,
+};
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Padding")]
+struct PaddingDef {
+    left: u16,
+    right: u16,
+    top: u16,
+    bottom: u16,
+}
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Alignment")]
+enum AlignmentDef {
+    Left,
+    Center,
+    Right,
+}
+/// `Option<Alignment>` through `AlignmentDef`
+#[derive(Serialize, Deserialize)]
+struct AlignmentWrap(
+    #[serde(with = "AlignmentDef")] Alignment,
+);
+fn serialize_opt_alignment<S: Serializer>(
+    alignment: &Option<Alignment>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    alignment.map(AlignmentWrap).serialize(serializer)
+}
+fn deserialize_opt_alignment<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Alignment>, D::Error> {
+    Ok(Option::<AlignmentWrap>::deserialize(deserializer)?
+        .map(|a| a.0))
+}
+/// a label is either plain text or a list of styled spans
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum LabelDef {
+    Text(String),
+    Line {
+        spans: Vec<SpanDef>,
+        #[serde(default)]
+        style: Style,
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            serialize_with = "serialize_opt_alignment",
+            deserialize_with = "deserialize_opt_alignment"
+        )]
+        alignment: Option<Alignment>,
+    },
+}
+#[derive(Serialize, Deserialize)]
+struct SpanDef {
+    content: String,
+    #[serde(default)]
+    style: Style,
+}
+/// a symbol set is either a preset name from the `registry` or inline symbols
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SetDef {
+    Name(String),
+    Set(Set),
+}
+#[derive(Serialize, Deserialize)]
+enum BgDef {
+    None,
+    Solid(Color),
+    Gradient,
+    GradientCustom(
+        #[serde(deserialize_with = "checked_spec")]
+        GradientSpec,
+    ),
+}
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct RuleDef {
+    #[serde(deserialize_with = "checked_opt_spec")]
+    gradient: Option<GradientSpec>,
+    #[serde(deserialize_with = "named_set")]
+    symbol_set: Set,
+    orientation: Orientation,
+    #[serde(with = "PaddingDef")]
+    padding: Padding,
+    vertical_alignment: VerticalAlignment,
+    #[serde(with = "AlignmentDef")]
+    horizontal_alignment: Alignment,
+    extra_rep_1: usize,
+    extra_rep_2: usize,
+    bg: BgDef,
+    area_margin: Margin,
+    label: Option<LabelDef>,
+    label_position: LabelPosition,
+    label_gap: (u16, u16),
+    label_style: LabelStyle,
+    merge_junctions: bool,
+    color_depth: ColorDepth,
+    dither: bool,
+    /// the phase is left out, it's the app's state
+    animation: Option<Animation>,
+    progress: Option<f32>,
+    smooth_progress: bool,
+    thickness: u16,
+    #[serde(deserialize_with = "named_sets")]
+    layers: Vec<Set>,
+    gradient_axis: GradientAxis,
+    gradient_field: Option<GradientField>,
+    markers: Vec<Marker>,
+    ticks: Option<Ticks>,
+}
+/// fails on unknown presets and bad colors while the deserializer
+/// still knows where the gradient is, so the error points at it
+fn checked_spec<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<GradientSpec, D::Error> {
+    let spec = GradientSpec::deserialize(deserializer)?;
+    spec.build().map_err(de::Error::custom)?;
+    Ok(spec)
+}
+fn checked_opt_spec<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<GradientSpec>, D::Error> {
+    Option::<GradientSpec>::deserialize(deserializer)?
+        .map(|spec| {
+            spec.build().map_err(de::Error::custom)?;
+            Ok(spec)
+        })
+        .transpose()
+}
+fn named_set<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Set, D::Error> {
+    SetDef::deserialize(deserializer)?
+        .resolve()
+        .map_err(de::Error::custom)
+}
+/// `named_set` for every layer
+fn named_sets<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Set>, D::Error> {
+    Vec::<SetDef>::deserialize(deserializer)?
+        .into_iter()
+        .map(|set| set.resolve().map_err(de::Error::custom))
+        .collect()
+}
+impl SetDef {
+    fn resolve(self) -> Result<Set, String> {
+        match self {
+            SetDef::Set(set) => Ok(set),
+            SetDef::Name(name) => registry::get(&name)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown symbol set preset `{name}`"
+                    )
+                }),
+        }
+    }
+}
+impl Default for RuleDef {
+    fn default() -> Self {
+        RuleDef::from_rule(&Rule::new())
+            .expect("the default rule has no gradient")
+    }
+}
+impl BgDef {
+    fn from_bg(bg: &Bg) -> Result<Self, &'static str> {
+        Ok(match bg {
+            Bg::None => Self::None,
+            Bg::Solid(c) => Self::Solid(*c),
+            Bg::Gradient => Self::Gradient,
+            Bg::GradientSpec(g) => {
+                Self::GradientCustom(g.spec().clone())
+            }
+            Bg::GradientCustom(_) => {
+                return Err(
+                    "`Bg::GradientCustom` can't be serialized, use `Bg::from_spec` instead",
+                );
+            }
+        })
+    }
+    fn into_bg(self) -> Result<Bg, String> {
+        Ok(match self {
+            Self::None => Bg::None,
+            Self::Solid(c) => Bg::Solid(c),
+            Self::Gradient => Bg::Gradient,
+            Self::GradientCustom(spec) => {
+                Bg::from_spec(spec)
+                    .map_err(|e| e.to_string())?
+            }
+        })
+    }
+}
+impl LabelDef {
+    fn from_line(line: &Line) -> Self {
+        if line.style == Style::default()
+            && line.alignment.is_none()
+            && line
+                .spans
+                .iter()
+                .all(|s| s.style == Style::default())
+        {
+            return Self::Text(line.to_string());
+        }
+        Self::Line {
+            spans: line
+                .spans
+                .iter()
+                .map(|s| SpanDef {
+                    content: s.content.to_string(),
+                    style: s.style,
+                })
+                .collect(),
+            style: line.style,
+            alignment: line.alignment,
+        }
+    }
+    fn into_line(self) -> Line<'static> {
+        match self {
+            Self::Text(text) => Line::from(text),
+            Self::Line {
+                spans,
+                style,
+                alignment,
+            } => Line {
+                alignment,
+                ..Line::from(
+                    spans
+                        .into_iter()
+                        .map(|s| {
+                            Span::styled(s.content, s.style)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .style(style)
+            },
+        }
+    }
+}
+impl RuleDef {
+    fn from_rule(
+        rule: &Rule,
+    ) -> Result<Self, &'static str> {
+        if rule.gradient.is_some()
+            && rule.gradient_spec.is_none()
+        {
+            return Err(
+                "gradients set with `with_gradient` can't be serialized, use `with_gradient_spec` instead",
+            );
+        }
+        Ok(Self {
+            gradient: rule.gradient_spec.clone(),
+            symbol_set: rule.symbol_set.clone(),
+            orientation: rule.orientation.clone(),
+            padding: rule.padding,
+            vertical_alignment: rule
+                .vertical_alignment
+                .clone(),
+            horizontal_alignment: rule.horizontal_alignment,
+            extra_rep_1: rule.extra_rep_1,
+            extra_rep_2: rule.extra_rep_2,
+            bg: BgDef::from_bg(&rule.bg)?,
+            area_margin: rule.area_margin,
+            label: rule
+                .label
+                .as_ref()
+                .map(LabelDef::from_line),
+            label_position: rule.label_position,
+            label_gap: rule.label_gap,
+            label_style: rule.label_style,
+            merge_junctions: rule.merge_junctions,
+            color_depth: rule.color_depth,
+            dither: rule.dither,
+            animation: rule.animation,
+            progress: rule.progress,
+            smooth_progress: rule.smooth_progress,
+            thickness: rule.thickness,
+            layers: rule.layers.clone(),
+            gradient_axis: rule.gradient_axis,
+            gradient_field: rule.gradient_field,
+            markers: rule.markers.clone(),
+            ticks: rule.ticks.clone(),
+        })
+    }
+    fn into_rule(self) -> Result<Rule, String> {
+        let mut rule = Rule::new();
+        if let Some(spec) = self.gradient {
+            rule = rule
+                .with_gradient_spec(spec)
+                .map_err(|e| e.to_string())?;
+        }
+        rule.symbol_set = self.symbol_set;
+        rule.orientation = self.orientation;
+        rule.padding = self.padding;
+        rule.vertical_alignment = self.vertical_alignment;
+        rule.horizontal_alignment =
+            self.horizontal_alignment;
+        rule.extra_rep_1 = self.extra_rep_1;
+        rule.extra_rep_2 = self.extra_rep_2;
+        rule.bg = self.bg.into_bg()?;
+        rule.area_margin = self.area_margin;
+        rule.label = self.label.map(LabelDef::into_line);
+        rule.label_position = self.label_position;
+        rule.label_gap = self.label_gap;
+        rule.label_style = self.label_style;
+        rule.merge_junctions = self.merge_junctions;
+        rule.color_depth = self.color_depth;
+        rule.dither = self.dither;
+        rule.animation = self.animation;
+        rule.progress = self.progress;
+        rule.smooth_progress = self.smooth_progress;
+        rule.thickness = self.thickness;
+        rule.layers = self.layers;
+        rule.gradient_axis = self.gradient_axis;
+        rule.gradient_field = self.gradient_field;
+        rule.markers = self.markers;
+        rule.ticks = self.ticks;
+        Ok(rule)
+    }
+}
+impl Serialize for Rule {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        RuleDef::from_rule(self)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        RuleDef::deserialize(deserializer)?
+            .into_rule()
+            .map_err(de::Error::custom)
+    }
+}
+impl Serialize for Bg {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        BgDef::from_bg(self)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for Bg {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        BgDef::deserialize(deserializer)?
+            .into_bg()
+            .map_err(de::Error::custom)
+    }
+}
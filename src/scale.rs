@@ -0,0 +1,327 @@
+//! ### Scale
+//! a ruler with numbered ticks, for timelines and plot axes.
+//! the track is a `Rule`, so it takes any `Set` and colors the ticks and labels with its gradient
+//! ```rust
+//!     // ┬────┬────┬────┬────┬
+//!     // 0s   5s  10s  15s 20s
+//!     let scale = Scale::new(0.0, 20.0, 5.0)
+//!         .format(|v| format!("{v}s"))
+//!         .with_gradient(colorgrad::preset::warm());
+//!     frame.render_widget(&scale, area);
+//! ```
+use crate::{Orientation, Rule, Set, Side};
+use colorgrad::Gradient;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Margin, Position, Rect},
+    style::Style,
+    widgets::{Widget, WidgetRef},
+};
+use std::borrow::Cow;
+use unicode_width::UnicodeWidthStr;
+/// the track of vertical scales
+const VERTICAL: Set = Set {
+    start: Cow::Borrowed("│"),
+    rep_1: Cow::Borrowed("│"),
+    center: Cow::Borrowed("│"),
+    rep_2: Cow::Borrowed("│"),
+    end: Cow::Borrowed("│"),
+};
+/// a ruler from `start` to `end` with a tick every `step`.
+/// the first and last cells of the rule are `start` and `end`,
+/// vertical scales run from the top down
+pub struct Scale {
+    /// the track. its padding is left out of the range
+    pub rule: Rule,
+    pub start: f64,
+    pub end: f64,
+    pub step: f64,
+    /// the side of the track the labels go on.
+    /// `Top` and `Left` are the same, and so are `Bottom` and `Right`
+    pub side: Side,
+    /// the glyph of the ticks. a tee pointing at the labels when `None`
+    pub tick_symbol: Option<Cow<'static, str>>,
+    /// applied on top of the color of the tick under the label
+    pub label_style: Style,
+    /// turns the value of a tick into its label
+    pub format: Box<dyn Fn(f64) -> String>,
+}
+impl Scale {
+    /// a horizontal scale with the labels below it.
+    /// the labels show as many decimals as `step` has
+    pub fn new(start: f64, end: f64, step: f64) -> Self {
+        let decimals = (0..6)
+            .find(|d| {
+                let scaled = step * 10f64.powi(*d);
+                (scaled - scaled.round()).abs() < 1e-9
+            })
+            .unwrap_or(6) as usize;
+        Self {
+            rule: Rule::default()
+                .area_margin(Margin::new(0, 0)),
+            start,
+            end,
+            step,
+            side: Side::Bottom,
+            tick_symbol: None,
+            label_style: Style::new(),
+            format: Box::new(move |v| {
+                format!("{v:.decimals$}")
+            }),
+        }
+        .align()
+    }
+    /// makes the scale vertical, with the labels on the right unless they were on top
+    pub fn vertical(mut self) -> Self {
+        self.rule = self.rule.with_set(VERTICAL).vertical();
+        self.align()
+    }
+    pub fn horizontal(mut self) -> Self {
+        self.rule =
+            self.rule.with_set(Set::default()).horizontal();
+        self.align()
+    }
+    /// replaces the track. the rule's alignment is kept as it is
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+    /// colors the track, the ticks and the labels
+    pub fn with_gradient<G: Gradient + 'static>(
+        mut self,
+        gradient: G,
+    ) -> Self {
+        self.rule = self.rule.with_gradient(gradient);
+        self
+    }
+    /// puts the labels on `side` and the track against the opposite one
+    pub fn labels(mut self, side: Side) -> Self {
+        self.side = side;
+        self.align()
+    }
+    pub fn tick_symbol(
+        mut self,
+        symbol: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.tick_symbol = Some(symbol.into());
+        self
+    }
+    pub fn label_style<S: Into<Style>>(
+        mut self,
+        style: S,
+    ) -> Self {
+        self.label_style = style.into();
+        self
+    }
+    /// sets how the values are written
+    /// ```rust
+    ///     Scale::new(0.0, 1.0, 0.25).format(|v| format!("{:.0}%", v * 100.0))
+    /// ```
+    pub fn format(
+        mut self,
+        format: impl Fn(f64) -> String + 'static,
+    ) -> Self {
+        self.format = Box::new(format);
+        self
+    }
+    /// whether the labels are below (right of) the track
+    fn after(&self) -> bool {
+        matches!(self.side, Side::Bottom | Side::Right)
+    }
+    /// leaves room for the labels on their side
+    fn align(mut self) -> Self {
+        let after = self.after();
+        match self.rule.orientation {
+            Orientation::Horizontal => {
+                self.rule.vertical_alignment = if after {
+                    crate::VerticalAlignment::Top
+                } else {
+                    crate::VerticalAlignment::Bottom
+                }
+            }
+            Orientation::Vertical => {
+                self.rule.horizontal_alignment = if after {
+                    Alignment::Left
+                } else {
+                    Alignment::Right
+                }
+            }
+        }
+        self
+    }
+    /// the cells the label of the tick at `pos` can take up, `None` when it's outside of `area`.
+    /// labels of horizontal scales are centered on their tick but kept inside `area`
+    fn label_area(
+        &self,
+        pos: Position,
+        width: u16,
+        area: Rect,
+    ) -> Option<Rect> {
+        let after = self.after();
+        let rect = match self.rule.orientation {
+            Orientation::Horizontal => {
+                let y = if after {
+                    pos.y.checked_add(1)?
+                } else {
+                    pos.y.checked_sub(1)?
+                };
+                if width > area.width {
+                    return None;
+                }
+                let x = pos
+                    .x
+                    .saturating_sub(
+                        width.saturating_sub(1) / 2,
+                    )
+                    .clamp(
+                        area.left(),
+                        area.right() - width,
+                    );
+                Rect::new(x, y, width, 1)
+            }
+            Orientation::Vertical if after => {
+                let x = pos.x.checked_add(1)?;
+                Rect::new(
+                    x,
+                    pos.y,
+                    area.right().saturating_sub(x),
+                    1,
+                )
+            }
+            Orientation::Vertical => {
+                let x = pos
+                    .x
+                    .saturating_sub(width)
+                    .max(area.left());
+                Rect::new(x, pos.y, pos.x - x, 1)
+            }
+        };
+        (!rect.is_empty()
+            && area.contains(rect.as_position()))
+        .then_some(rect)
+    }
+    /// where every tick lands in `area` and its value.
+    /// ticks closer than a cell to each other are thinned out
+    pub fn ticks(
+        &self,
+        area: Rect,
+    ) -> Vec<(Position, f64)> {
+        let layouts = self.rule.layouts(area);
+        // the row next to the labels
+        let layout = if self.after() {
+            layouts.last()
+        } else {
+            layouts.first()
+        };
+        let Some(layout) = layout else {
+            return Vec::new();
+        };
+        let cells = layout.start.start..layout.end.end;
+        let span = self.end - self.start;
+        let count = span / self.step;
+        if cells.is_empty()
+            || !count.is_finite()
+            || count < 0.0
+        {
+            return Vec::new();
+        }
+        let last = (cells.len() - 1) as f64;
+        // at least a cell between ticks
+        let stride = if span == 0.0 {
+            1
+        } else {
+            (span / (self.step * last))
+                .abs()
+                .ceil()
+                .max(1.0) as usize
+        };
+        let mut ticks: Vec<(Position, f64)> = Vec::new();
+        for i in
+            (0..=(count + 1e-9) as usize).step_by(stride)
+        {
+            let value = self.start + i as f64 * self.step;
+            let ratio = if span == 0.0 {
+                0.0
+            } else {
+                (value - self.start) / span
+            };
+            let pos = layout.position(
+                cells.start
+                    + (ratio * last).round() as usize,
+            );
+            if ticks.last().is_some_and(|(p, _)| *p == pos)
+            {
+                continue;
+            }
+            ticks.push((pos, value));
+        }
+        ticks.retain(|(pos, _)| area.contains(*pos));
+        ticks
+    }
+}
+impl Widget for Scale {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.render_ref(area, buf);
+    }
+}
+impl WidgetRef for Scale {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        self.rule.render_ref(area, buf);
+        let tick = self.tick_symbol.as_deref().unwrap_or(
+            match (&self.rule.orientation, self.after()) {
+                (Orientation::Horizontal, true) => "┬",
+                (Orientation::Horizontal, false) => "┴",
+                (Orientation::Vertical, true) => "├",
+                (Orientation::Vertical, false) => "┤",
+            },
+        );
+        let mut labels = Vec::new();
+        for (pos, value) in self.ticks(area) {
+            let Some(cell) = buf.cell_mut(pos) else {
+                continue;
+            };
+            cell.set_symbol(tick);
+            let style = Style::new()
+                .fg(cell.fg)
+                .patch(self.label_style);
+            let text = (self.format)(value);
+            if let Some(rect) = self.label_area(
+                pos,
+                text.width() as u16,
+                area,
+            ) {
+                labels.push((rect, text, style));
+            }
+        }
+        // every `stride`th label, the fewest left out that keeps them apart
+        let apart = |a: &Rect, b: &Rect| match self
+            .rule
+            .orientation
+        {
+            Orientation::Horizontal => b.left() > a.right(),
+            Orientation::Vertical => b.top() >= a.bottom(),
+        };
+        let stride = (1..labels.len())
+            .find(|stride| {
+                let rects: Vec<&Rect> = labels
+                    .iter()
+                    .step_by(*stride)
+                    .map(|(rect, ..)| rect)
+                    .collect();
+                rects.windows(2).all(|w| apart(w[0], w[1]))
+            })
+            .unwrap_or(labels.len().max(1));
+        for (rect, text, style) in
+            labels.into_iter().step_by(stride)
+        {
+            buf.set_stringn(
+                rect.x,
+                rect.y,
+                text,
+                rect.width as usize,
+                style,
+            );
+        }
+    }
+}
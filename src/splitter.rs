@@ -0,0 +1,295 @@
+//! ### Splitter
+//! a rule between two panes that can be dragged with the mouse or nudged with the keyboard.
+//! the state keeps the split, feed it to a `Layout` to size the panes
+//! ```rust
+//!     let mut state = SplitterState::new(30);
+//!     loop {
+//!         terminal.draw(|f| {
+//!             let [left, splitter, right] =
+//!                 Layout::horizontal(state.constraints()).areas(f.area());
+//!             f.render_widget(left_pane, left);
+//!             f.render_widget(right_pane, right);
+//!             f.render_stateful_widget(Splitter::vertical(), f.area(), &mut state);
+//!         })?;
+//!         match event::read()? {
+//!             Event::Mouse(mouse) => { state.handle_mouse(mouse); }
+//!             Event::Key(key) => { state.handle_key(key); }
+//!             _ => {}
+//!         }
+//!     }
+//! ```
+use crate::{Orientation, Rule, presets};
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, MouseButton,
+    MouseEvent, MouseEventKind,
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Margin, Position, Rect},
+    style::{Modifier, Style},
+    widgets::{
+        StatefulWidget, StatefulWidgetRef, WidgetRef,
+    },
+};
+/// where the splitter is and what the mouse and keyboard are doing with it
+#[derive(Clone, Debug, PartialEq, Hash)]
+pub struct SplitterState {
+    /// cells from the start of the area to the splitter,
+    /// the size of the first pane
+    pub position: u16,
+    /// the smallest position the splitter can be moved to
+    pub min: u16,
+    /// the largest position the splitter can be moved to.
+    /// it never leaves the area either way
+    pub max: u16,
+    /// how many cells the arrow keys move it
+    pub step: u16,
+    /// the mouse is over the splitter
+    pub hovered: bool,
+    /// the splitter is being dragged
+    pub dragging: bool,
+    /// the arrow keys move the splitter.
+    /// clicking it focuses it, clicking elsewhere or `Esc` unfocuses it
+    pub focused: bool,
+    /// where the splitter was last rendered
+    area: Rect,
+    orientation: Orientation,
+}
+impl Default for SplitterState {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+impl SplitterState {
+    pub fn new(position: u16) -> Self {
+        Self {
+            position,
+            min: 0,
+            max: u16::MAX,
+            step: 1,
+            hovered: false,
+            dragging: false,
+            focused: false,
+            area: Rect::ZERO,
+            orientation: Orientation::Vertical,
+        }
+    }
+    pub fn min(mut self, min: u16) -> Self {
+        self.min = min;
+        self
+    }
+    pub fn max(mut self, max: u16) -> Self {
+        self.max = max;
+        self
+    }
+    pub fn step(mut self, step: u16) -> Self {
+        self.step = step;
+        self
+    }
+    /// the area the splitter was last rendered in
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+    /// the first pane, the splitter and the second pane, for a `Layout` in the direction the splitter moves
+    /// ```rust
+    ///     let [left, _, right] = Layout::horizontal(state.constraints()).areas(area);
+    /// ```
+    pub fn constraints(&self) -> [Constraint; 3] {
+        [
+            Constraint::Length(self.position),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ]
+    }
+    /// the cells of the splitter
+    pub fn splitter_area(&self) -> Rect {
+        let a = self.area;
+        match self.orientation {
+            Orientation::Vertical => Rect::new(
+                a.x.saturating_add(self.position),
+                a.y,
+                a.width.min(1),
+                a.height,
+            ),
+            Orientation::Horizontal => Rect::new(
+                a.x,
+                a.y.saturating_add(self.position),
+                a.width,
+                a.height.min(1),
+            ),
+        }
+    }
+    /// moves the splitter to `position`, kept between `min`, `max` and the ends of the area.
+    /// returns the new position if it moved
+    pub fn move_to(
+        &mut self,
+        position: u16,
+    ) -> Option<u16> {
+        let len = match self.orientation {
+            Orientation::Vertical => self.area.width,
+            Orientation::Horizontal => self.area.height,
+        };
+        let max = self.max.min(len.saturating_sub(1));
+        let position =
+            position.min(max).max(self.min.min(max));
+        (position != self.position).then(|| {
+            self.position = position;
+            position
+        })
+    }
+    /// updates the hover and drag state and drags the splitter.
+    /// returns the new position if it moved
+    pub fn handle_mouse(
+        &mut self,
+        event: MouseEvent,
+    ) -> Option<u16> {
+        let pos = Position::new(event.column, event.row);
+        self.hovered = self.splitter_area().contains(pos);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.dragging = self.hovered;
+                self.focused = self.hovered;
+                None
+            }
+            MouseEventKind::Drag(MouseButton::Left)
+                if self.dragging =>
+            {
+                let offset = match self.orientation {
+                    Orientation::Vertical => {
+                        pos.x.saturating_sub(self.area.x)
+                    }
+                    Orientation::Horizontal => {
+                        pos.y.saturating_sub(self.area.y)
+                    }
+                };
+                self.move_to(offset)
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.dragging = false;
+                None
+            }
+            _ => None,
+        }
+    }
+    /// moves the splitter with the arrow keys along its axis, `Home` and `End` while it's focused.
+    /// returns the new position if it moved
+    pub fn handle_key(
+        &mut self,
+        event: KeyEvent,
+    ) -> Option<u16> {
+        if !self.focused
+            || event.kind == KeyEventKind::Release
+        {
+            return None;
+        }
+        let back = match self.orientation {
+            Orientation::Vertical => KeyCode::Left,
+            Orientation::Horizontal => KeyCode::Up,
+        };
+        let forward = match self.orientation {
+            Orientation::Vertical => KeyCode::Right,
+            Orientation::Horizontal => KeyCode::Down,
+        };
+        match event.code {
+            code if code == back => self.move_to(
+                self.position.saturating_sub(self.step),
+            ),
+            code if code == forward => self.move_to(
+                self.position.saturating_add(self.step),
+            ),
+            KeyCode::Home => self.move_to(self.min),
+            KeyCode::End => self.move_to(self.max),
+            KeyCode::Esc => {
+                self.focused = false;
+                None
+            }
+            _ => None,
+        }
+    }
+}
+/// a rule drawn across the area at the position in its `SplitterState`.
+/// vertical splitters sit between a left and a right pane, horizontal ones between a top and a bottom pane
+pub struct Splitter {
+    /// drawn in the cells of the splitter. its orientation is the splitter's
+    pub rule: Rule,
+    /// applied on top of the rule while the mouse is over it
+    pub hover_style: Style,
+    /// applied on top of the rule while it's dragged or focused
+    pub active_style: Style,
+}
+impl Default for Splitter {
+    fn default() -> Self {
+        Self::vertical()
+    }
+}
+impl Splitter {
+    /// a `│` splitter between a left and a right pane
+    pub fn vertical() -> Self {
+        Self::new(
+            Rule::from_set(presets::borders::plain::LEFT)
+                .start("│")
+                .end("│")
+                .vertical(),
+        )
+    }
+    /// a `─` splitter between a top and a bottom pane
+    pub fn horizontal() -> Self {
+        Self::new(Rule::default())
+    }
+    pub fn new(rule: Rule) -> Self {
+        Self {
+            rule: rule.area_margin(Margin::new(0, 0)),
+            hover_style: Style::new()
+                .add_modifier(Modifier::BOLD),
+            active_style: Style::new()
+                .add_modifier(Modifier::BOLD)
+                .add_modifier(Modifier::REVERSED),
+        }
+    }
+    pub fn hover_style<S: Into<Style>>(
+        mut self,
+        style: S,
+    ) -> Self {
+        self.hover_style = style.into();
+        self
+    }
+    pub fn active_style<S: Into<Style>>(
+        mut self,
+        style: S,
+    ) -> Self {
+        self.active_style = style.into();
+        self
+    }
+}
+impl StatefulWidget for Splitter {
+    type State = SplitterState;
+    fn render(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut Self::State,
+    ) {
+        self.render_ref(area, buf, state);
+    }
+}
+impl StatefulWidgetRef for Splitter {
+    type State = SplitterState;
+    fn render_ref(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut Self::State,
+    ) {
+        state.area = area;
+        state.orientation = self.rule.orientation.clone();
+        // the area may have shrunk since the last frame
+        state.move_to(state.position);
+        let splitter = state.splitter_area();
+        WidgetRef::render_ref(&self.rule, splitter, buf);
+        if state.dragging || state.focused {
+            buf.set_style(splitter, self.active_style);
+        } else if state.hovered {
+            buf.set_style(splitter, self.hover_style);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! ### Rule state
+//! what changes between frames, kept outside of the rule so it can be built once.
+//! render the rule with `render_stateful_widget` to use it
+//! ```rust
+//!     let rule = Rule::default()
+//!         .with_gradient(colorgrad::preset::sinebow())
+//!         .animation(Animation::scroll());
+//!     let mut state = RuleState::new();
+//!     loop {
+//!         state.tick(frame_time);
+//!         terminal.draw(|f| f.render_stateful_widget(&rule, f.area(), &mut state))?;
+//!         if let Event::Mouse(mouse) = event::read()? {
+//!             state.hover(Position::new(mouse.column, mouse.row));
+//!         }
+//!     }
+//! ```
+use crate::{
+    Orientation, Rule, RuleLayout, RulePart, Set,
+    VerticalAlignment,
+};
+use ratatui::{
+    layout::{Alignment, Margin, Position, Rect},
+    widgets::Padding,
+};
+use std::time::Duration;
+/// the state of a rule rendered as a `StatefulWidget`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RuleState {
+    /// the animation clock, used instead of `Rule::phase`
+    pub phase: f32,
+    /// used instead of `Rule::progress` when set
+    pub progress: Option<f32>,
+    /// the mouse is over the rule
+    pub hovered: bool,
+    pub focused: bool,
+    /// the area of the last render
+    pub(crate) area: Rect,
+    /// the layouts of the last render
+    pub(crate) layouts: Vec<RuleLayout>,
+    /// what the layouts were computed from
+    pub(crate) key: Option<LayoutKey>,
+}
+/// everything the layouts of a rule depend on
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LayoutKey {
+    area: Rect,
+    orientation: Orientation,
+    padding: Padding,
+    vertical_alignment: VerticalAlignment,
+    horizontal_alignment: Alignment,
+    area_margin: Margin,
+    extra_rep: (usize, usize),
+    thickness: u16,
+    symbol_set: Set,
+    layers: Vec<Set>,
+}
+impl LayoutKey {
+    fn new(rule: &Rule, area: Rect) -> Self {
+        Self {
+            area,
+            orientation: rule.orientation.clone(),
+            padding: rule.padding,
+            vertical_alignment: rule
+                .vertical_alignment
+                .clone(),
+            horizontal_alignment: rule.horizontal_alignment,
+            area_margin: rule.area_margin,
+            extra_rep: (rule.extra_rep_1, rule.extra_rep_2),
+            thickness: rule.thickness,
+            symbol_set: rule.symbol_set.clone(),
+            layers: rule.layers.clone(),
+        }
+    }
+    /// compares without cloning the sets of the rule
+    fn matches(&self, rule: &Rule, area: Rect) -> bool {
+        self.area == area
+            && self.orientation == rule.orientation
+            && self.padding == rule.padding
+            && self.vertical_alignment
+                == rule.vertical_alignment
+            && self.horizontal_alignment
+                == rule.horizontal_alignment
+            && self.area_margin == rule.area_margin
+            && self.extra_rep
+                == (rule.extra_rep_1, rule.extra_rep_2)
+            && self.thickness == rule.thickness
+            && self.symbol_set == rule.symbol_set
+            && self.layers == rule.layers
+    }
+}
+impl RuleState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn phase(mut self, phase: f32) -> Self {
+        self.phase = phase;
+        self
+    }
+    pub fn progress(mut self, ratio: f32) -> Self {
+        self.progress = Some(ratio);
+        self
+    }
+    /// moves the animation clock forward
+    pub fn tick(&mut self, elapsed: Duration) {
+        self.phase += elapsed.as_secs_f32();
+    }
+    /// the area the rule was last rendered in
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+    /// the layout of every row of the last render
+    pub fn layouts(&self) -> &[RuleLayout] {
+        &self.layouts
+    }
+    /// the part of the rule at `pos` in the last render
+    pub fn part_at(
+        &self,
+        pos: Position,
+    ) -> Option<RulePart> {
+        self.layouts.iter().find_map(|l| l.part_at(pos))
+    }
+    /// sets `hovered` from the mouse position, returns whether it changed
+    pub fn hover(&mut self, pos: Position) -> bool {
+        let hovered = self.part_at(pos).is_some();
+        let changed = hovered != self.hovered;
+        self.hovered = hovered;
+        changed
+    }
+    /// the cached layouts of `rule` in `area`, recomputed when either changed
+    pub(crate) fn layouts_for(
+        &mut self,
+        rule: &Rule,
+        area: Rect,
+    ) -> &[RuleLayout] {
+        if !self
+            .key
+            .as_ref()
+            .is_some_and(|key| key.matches(rule, area))
+        {
+            self.key = Some(LayoutKey::new(rule, area));
+            self.area = area;
+            self.layouts = rule.layouts(area);
+        }
+        &self.layouts
+    }
+}
@@ -0,0 +1,393 @@
+//! ### Gradient text
+//! colors any text with a gradient while keeping the style of every span,
+//! so `Block` titles, `Paragraph`s and `Tabs` can match the rules around them
+//! ```rust
+//!     let warm = colorgrad::preset::warm();
+//!     let title = Line::from(vec!["Logs".bold(), " (3)".italic()])
+//!         .gradient(&warm, BgMode::None);
+//!     let text = Text::from("first line\nsecond line")
+//!         .gradient_vertical(&warm, BgMode::None);
+//!     let tabs = Tabs::new(["Home", "Logs"].map(|t| t.gradient_words(&warm, BgMode::None)));
+//!     Paragraph::new(text).block(Block::bordered().title(title))
+//! ```
+use crate::Bg;
+use colorgrad::{Gradient, preset};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span, Text},
+};
+use std::borrow::Cow;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+/// the background of gradient text
+#[derive(Clone, Copy, Default)]
+pub enum BgMode<'a> {
+    /// leaves the background of every span as it is
+    #[default]
+    None,
+    Solid(Color),
+    /// the same colors as the text
+    Same,
+    /// another gradient, sampled at the same cells as the text
+    Gradient(&'a dyn Gradient),
+}
+impl<'a> From<&'a Bg> for BgMode<'a> {
+    fn from(bg: &'a Bg) -> Self {
+        match bg {
+            Bg::None => BgMode::None,
+            Bg::Solid(c) => BgMode::Solid(*c),
+            Bg::Gradient => BgMode::Same,
+            Bg::GradientCustom(g) => {
+                BgMode::Gradient(g.as_ref())
+            }
+            Bg::GradientSpec(g) => {
+                BgMode::Gradient(g.gradient())
+            }
+        }
+    }
+}
+impl From<Color> for BgMode<'_> {
+    fn from(c: Color) -> Self {
+        BgMode::Solid(c)
+    }
+}
+/// gradients for anything that turns into a `Line` (`&str`, `String`, `Span`, `Vec<Span>`, `Line`).
+/// every grapheme becomes its own span and keeps the style and modifiers of the span it came from
+pub trait GradientExt<'a> {
+    /// colors the text with `gradient`, from its first cell to its last.
+    /// wide graphemes take the color of their first cell
+    /// ```rust
+    ///     "Rainbow Text".gradient(&colorgrad::preset::rainbow(), Color::Black)
+    /// ```
+    fn gradient<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Line<'a>;
+    /// one color per word, from the first word to the last
+    fn gradient_words<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Line<'a>;
+    /// the rainbow, spread over the graphemes
+    fn rainbow(self) -> Line<'a>;
+}
+impl<'a, T: Into<Line<'a>>> GradientExt<'a> for T {
+    fn gradient<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Line<'a> {
+        let line = self.into();
+        let bg = bg.into();
+        let palette =
+            Palette::new(gradient, bg, line.width());
+        paint(line, &palette, bg, |cell, _| cell)
+    }
+    fn gradient_words<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Line<'a> {
+        let line = self.into();
+        let bg = bg.into();
+        let palette =
+            Palette::new(gradient, bg, word_count(&line));
+        paint(line, &palette, bg, word_slots())
+    }
+    fn rainbow(self) -> Line<'a> {
+        let line = self.into();
+        let palette = Palette::new(
+            &preset::rainbow(),
+            BgMode::None,
+            grapheme_count(&line),
+        );
+        paint(
+            line,
+            &palette,
+            BgMode::None,
+            grapheme_slots(),
+        )
+    }
+}
+/// gradients for multi-line `Text`, e.g. the content of a `Paragraph`
+pub trait TextGradientExt<'a> {
+    /// the whole gradient on every line
+    fn gradient_lines<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Text<'a>;
+    /// one gradient running through every line, in reading order
+    fn gradient_across<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Text<'a>;
+    /// one color per line, from the top line to the bottom one
+    fn gradient_vertical<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Text<'a>;
+    /// one color per word, from the first word of the text to the last
+    fn gradient_words<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Text<'a>;
+    /// the rainbow, spread over every grapheme of the text
+    fn rainbow(self) -> Text<'a>;
+}
+impl<'a> TextGradientExt<'a> for Text<'a> {
+    fn gradient_lines<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Text<'a> {
+        let bg = bg.into();
+        let lines = self
+            .lines
+            .into_iter()
+            .map(|line| line.gradient(gradient, bg))
+            .collect();
+        Text { lines, ..self }
+    }
+    fn gradient_across<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Text<'a> {
+        let bg = bg.into();
+        let width =
+            self.lines.iter().map(Line::width).sum();
+        let palette = Palette::new(gradient, bg, width);
+        let mut offset = 0;
+        let lines = self
+            .lines
+            .into_iter()
+            .map(|line| {
+                let start = offset;
+                offset += line.width();
+                paint(line, &palette, bg, |cell, _| {
+                    start + cell
+                })
+            })
+            .collect();
+        Text { lines, ..self }
+    }
+    fn gradient_vertical<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Text<'a> {
+        let bg = bg.into();
+        let palette =
+            Palette::new(gradient, bg, self.lines.len());
+        let lines = self
+            .lines
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                paint(line, &palette, bg, |_, _| i)
+            })
+            .collect();
+        Text { lines, ..self }
+    }
+    fn gradient_words<'g>(
+        self,
+        gradient: &dyn Gradient,
+        bg: impl Into<BgMode<'g>>,
+    ) -> Text<'a> {
+        let bg = bg.into();
+        let words = self.lines.iter().map(word_count).sum();
+        let palette = Palette::new(gradient, bg, words);
+        // words don't go on over line breaks
+        let mut slots = word_slots();
+        let lines = self
+            .lines
+            .into_iter()
+            .map(|line| {
+                let line =
+                    paint(line, &palette, bg, &mut slots);
+                slots(0, " ");
+                line
+            })
+            .collect();
+        Text { lines, ..self }
+    }
+    fn rainbow(self) -> Text<'a> {
+        let graphemes =
+            self.lines.iter().map(grapheme_count).sum();
+        let palette = Palette::new(
+            &preset::rainbow(),
+            BgMode::None,
+            graphemes,
+        );
+        let mut slots = grapheme_slots();
+        let lines = self
+            .lines
+            .into_iter()
+            .map(|line| {
+                paint(
+                    line,
+                    &palette,
+                    BgMode::None,
+                    &mut slots,
+                )
+            })
+            .collect();
+        Text { lines, ..self }
+    }
+}
+/// the colors of a gradient and of a bg gradient at every slot,
+/// sampled once and reused for every grapheme
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Palette {
+    fg: Vec<Color>,
+    bg: Vec<Color>,
+}
+impl Palette {
+    pub(crate) fn new(
+        gradient: &dyn Gradient,
+        bg: BgMode,
+        slots: usize,
+    ) -> Self {
+        let bg = match bg {
+            BgMode::Gradient(g) => {
+                g.colors(slots).iter().map(rgb).collect()
+            }
+            _ => Vec::new(),
+        };
+        Self {
+            fg: gradient
+                .colors(slots)
+                .iter()
+                .map(rgb)
+                .collect(),
+            bg,
+        }
+    }
+    /// the style of slot `i`, `None` when there's no such slot
+    pub(crate) fn style(
+        &self,
+        i: usize,
+        bg: BgMode,
+    ) -> Option<Style> {
+        let fg = *self.fg.get(i)?;
+        Some(with_bg(
+            Style::new().fg(fg),
+            bg,
+            self.bg.get(i).copied(),
+        ))
+    }
+}
+/// puts the bg on `style`. `Same` copies the fg of the style and is left out when it has none,
+/// `sampled` is the color of the bg gradient at the cell
+pub(crate) fn with_bg(
+    style: Style,
+    bg: BgMode,
+    sampled: Option<Color>,
+) -> Style {
+    match (bg, style.fg, sampled) {
+        (BgMode::Solid(c), ..) => style.bg(c),
+        (BgMode::Same, Some(fg), _) => style.bg(fg),
+        (BgMode::Gradient(_), _, Some(c)) => style.bg(c),
+        _ => style,
+    }
+}
+/// restyles every grapheme of `line` with the color in its slot.
+/// the palette has a color for each slot and `slot` picks one
+/// from the cell the grapheme starts at and the grapheme itself
+pub(crate) fn paint<'a>(
+    line: Line<'a>,
+    palette: &Palette,
+    bg: BgMode,
+    mut slot: impl FnMut(usize, &str) -> usize,
+) -> Line<'a> {
+    let slots = palette.fg.len();
+    let mut cell = 0;
+    let mut spans = Vec::with_capacity(line.width());
+    for span in line.spans {
+        for g in graphemes(span.content) {
+            // zero-width graphemes take the color of the cell before them
+            let i =
+                slot(cell, &g).min(slots.saturating_sub(1));
+            cell += g.width();
+            let style = match palette.style(i, bg) {
+                Some(style) => span.style.patch(style),
+                None => span.style,
+            };
+            spans.push(Span::styled(g, style));
+        }
+    }
+    Line { spans, ..line }
+}
+/// numbers the words, whitespace keeps the number of the word before it
+fn word_slots() -> impl FnMut(usize, &str) -> usize {
+    let (mut words, mut in_word) = (0usize, false);
+    move |_, g| {
+        let space = g.trim().is_empty();
+        if !space && !in_word {
+            words += 1;
+        }
+        in_word = !space;
+        words.saturating_sub(1)
+    }
+}
+/// numbers the graphemes
+fn grapheme_slots() -> impl FnMut(usize, &str) -> usize {
+    let mut n = 0;
+    move |_, _| {
+        n += 1;
+        n - 1
+    }
+}
+fn word_count(line: &Line) -> usize {
+    line.iter()
+        .map(|s| s.content.as_ref())
+        .collect::<String>()
+        .split_whitespace()
+        .count()
+}
+fn grapheme_count(line: &Line) -> usize {
+    line.iter()
+        .map(|s| s.content.graphemes(true).count())
+        .sum()
+}
+/// splits text into graphemes, borrowing from it when it's borrowed
+fn graphemes(content: Cow<'_, str>) -> Vec<Cow<'_, str>> {
+    match content {
+        Cow::Borrowed(s) => {
+            s.graphemes(true).map(Cow::Borrowed).collect()
+        }
+        Cow::Owned(s) => s
+            .graphemes(true)
+            .map(|g| Cow::Owned(g.to_string()))
+            .collect(),
+    }
+}
+pub(crate) fn rgb(c: &colorgrad::Color) -> Color {
+    Color::Rgb(
+        (c.r * 255.0) as u8,
+        (c.g * 255.0) as u8,
+        (c.b * 255.0) as u8,
+    )
+}
+/// turns owned gradients, boxed ones and references to them into `&dyn Gradient`.
+/// lets `generate_gradient_text!` take any of them
+#[doc(hidden)]
+pub trait AsDynGradient {
+    fn as_dyn_gradient(&self) -> &dyn Gradient;
+}
+impl<G: Gradient> AsDynGradient for G {
+    fn as_dyn_gradient(&self) -> &dyn Gradient {
+        self
+    }
+}
+impl AsDynGradient for dyn Gradient {
+    fn as_dyn_gradient(&self) -> &dyn Gradient {
+        self
+    }
+}
@@ -0,0 +1,323 @@
+//! ### Rule themes
+//! loads named rules from TOML, JSON or RON files
+//! ```toml
+//! [section]
+//! label = "Logs"
+//! gradient = { preset = "warm" }
+//! symbol_set = { start = "├", end = "┤" }
+//!
+//! [sidebar-divider]
+//! orientation = "Vertical"
+//! symbol_set = "borders::plain::LEFT"
+//! bg = { Solid = "#1e1e2e" }
+//! ```
+//! ```rust
+//!     let theme = Theme::load("rules.toml")?;
+//!     theme["section"].render_ref(area, buf);
+//! ```
+use crate::Rule;
+#[cfg(feature = "watch")]
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    ops::Index,
+    path::{Path, PathBuf},
+};
+/// file formats a theme can be loaded from
+#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+pub enum ThemeFormat {
+    Toml,
+    Json,
+    Ron,
+}
+impl ThemeFormat {
+    /// guesses the format from the file extension
+    pub fn from_path<P: AsRef<Path>>(
+        path: P,
+    ) -> Option<Self> {
+        match path.as_ref().extension()?.to_str()? {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            "ron" => Some(Self::Ron),
+            _ => None,
+        }
+    }
+}
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(PathBuf, io::Error),
+    /// the extension isn't `.toml`, `.json` or `.ron`
+    UnknownFormat(PathBuf),
+    /// invalid syntax, unknown preset names, bad colors...
+    /// `line` and `column` start at 1
+    Parse {
+        path: Option<PathBuf>,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+}
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(path, e) => {
+                write!(f, "{}: {e}", path.display())
+            }
+            Self::UnknownFormat(path) => write!(
+                f,
+                "{}: unknown theme format (expected .toml, .json or .ron)",
+                path.display()
+            ),
+            Self::Parse {
+                path,
+                line,
+                column,
+                message,
+            } => {
+                if let Some(path) = path {
+                    write!(f, "{}:", path.display())?;
+                }
+                if let Some(line) = line {
+                    write!(f, "{line}:")?;
+                }
+                if let Some(column) = column {
+                    write!(f, "{column}:")?;
+                }
+                if path.is_some() || line.is_some() {
+                    write!(f, " ")?;
+                }
+                write!(f, "{message}")
+            }
+        }
+    }
+}
+impl std::error::Error for ThemeError {
+    fn source(
+        &self,
+    ) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+impl ThemeError {
+    fn parse(
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    ) -> Self {
+        Self::Parse {
+            path: None,
+            line,
+            column,
+            message,
+        }
+    }
+    fn with_path(mut self, p: &Path) -> Self {
+        if let Self::Parse { path, .. } = &mut self {
+            *path = Some(p.to_path_buf());
+        }
+        self
+    }
+}
+/// named rules loaded from a theme file
+#[derive(Default)]
+pub struct Theme {
+    pub rules: BTreeMap<String, Rule>,
+}
+impl Theme {
+    /// parses a theme from a string
+    pub fn parse(
+        src: &str,
+        format: ThemeFormat,
+    ) -> Result<Self, ThemeError> {
+        let rules = match format {
+            ThemeFormat::Toml => toml::from_str(src)
+                .map_err(|e| {
+                    let (line, column) = match e.span() {
+                        Some(span) => {
+                            line_column(src, span.start)
+                        }
+                        None => (None, None),
+                    };
+                    ThemeError::parse(
+                        line,
+                        column,
+                        e.message().to_string(),
+                    )
+                })?,
+            ThemeFormat::Json => serde_json::from_str(src)
+                .map_err(|e| {
+                    let message = e.to_string();
+                    let suffix = format!(
+                        " at line {} column {}",
+                        e.line(),
+                        e.column()
+                    );
+                    ThemeError::parse(
+                        Some(e.line()),
+                        Some(e.column()),
+                        message
+                            .strip_suffix(&suffix)
+                            .unwrap_or(&message)
+                            .to_string(),
+                    )
+                })?,
+            ThemeFormat::Ron => ron::from_str(src)
+                .map_err(|e| {
+                    ThemeError::parse(
+                        Some(e.position.line),
+                        Some(e.position.col),
+                        e.code.to_string(),
+                    )
+                })?,
+        };
+        Ok(Self { rules })
+    }
+    /// loads a theme file. the format is picked from the extension
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let format = ThemeFormat::from_path(path)
+            .ok_or_else(|| {
+                ThemeError::UnknownFormat(
+                    path.to_path_buf(),
+                )
+            })?;
+        let src =
+            fs::read_to_string(path).map_err(|e| {
+                ThemeError::Io(path.to_path_buf(), e)
+            })?;
+        Self::parse(&src, format)
+            .map_err(|e| e.with_path(path))
+    }
+    pub fn get(&self, name: &str) -> Option<&Rule> {
+        self.rules.get(name)
+    }
+    /// removes a rule from the theme to get an owned `Rule`
+    pub fn take(&mut self, name: &str) -> Option<Rule> {
+        self.rules.remove(name)
+    }
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.rules.keys().map(String::as_str)
+    }
+    /// watches a theme file for changes.
+    /// `on_change` is called from the watcher thread (e.g. to wake up the event loop),
+    /// the theme itself is reloaded on your thread with `ThemeWatcher::reload`
+    /// ```rust
+    ///     let watcher = Theme::watch("rules.toml", || {})?;
+    ///     loop {
+    ///         if let Some(new_theme) = watcher.reload() {
+    ///             theme = new_theme?;
+    ///         }
+    ///         terminal.draw(|f| theme["section"].render_ref(f.area(), f.buffer_mut()))?;
+    ///     }
+    /// ```
+    #[cfg(feature = "watch")]
+    pub fn watch<P, F>(
+        path: P,
+        mut on_change: F,
+    ) -> Result<ThemeWatcher, ThemeError>
+    where
+        P: AsRef<Path>,
+        F: FnMut() + Send + 'static,
+    {
+        use notify::{RecursiveMode, Watcher};
+        let path = path.as_ref().to_path_buf();
+        let changed = Arc::new(AtomicBool::new(false));
+        let notify_err = |e: notify::Error| {
+            ThemeError::Io(
+                path.clone(),
+                io::Error::other(e),
+            )
+        };
+        let (file_name, flag) = (
+            path.file_name().map(|n| n.to_owned()),
+            changed.clone(),
+        );
+        let mut watcher = notify::recommended_watcher(
+            move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else {
+                    return;
+                };
+                // editors often replace the file instead of writing to it,
+                // so the parent directory is watched and filtered by name
+                let touches_file =
+                    event.paths.iter().any(|p| {
+                        p.file_name()
+                            == file_name.as_deref()
+                    });
+                if touches_file
+                    && (event.kind.is_modify()
+                        || event.kind.is_create())
+                {
+                    flag.store(true, Ordering::Release);
+                    on_change();
+                }
+            },
+        )
+        .map_err(notify_err)?;
+        let dir = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => Path::new("."),
+        };
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(notify_err)?;
+        Ok(ThemeWatcher {
+            path,
+            changed,
+            _watcher: watcher,
+        })
+    }
+}
+impl Index<&str> for Theme {
+    type Output = Rule;
+    /// panics if the theme has no rule called `name`
+    fn index(&self, name: &str) -> &Rule {
+        self.get(name).unwrap_or_else(|| {
+            panic!("theme has no rule called `{name}`")
+        })
+    }
+}
+/// keeps watching a theme file until dropped
+#[cfg(feature = "watch")]
+pub struct ThemeWatcher {
+    path: PathBuf,
+    changed: Arc<AtomicBool>,
+    _watcher: notify::RecommendedWatcher,
+}
+#[cfg(feature = "watch")]
+impl ThemeWatcher {
+    /// reloads the theme if the file changed since the last call
+    pub fn reload(
+        &self,
+    ) -> Option<Result<Theme, ThemeError>> {
+        self.changed
+            .swap(false, Ordering::AcqRel)
+            .then(|| Theme::load(&self.path))
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+/// 1-based line and column of a byte offset
+fn line_column(
+    src: &str,
+    offset: usize,
+) -> (Option<usize>, Option<usize>) {
+    let before = &src[..offset.min(src.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |l| l.chars().count())
+        + 1;
+    (Some(line), Some(column))
+}